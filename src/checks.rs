@@ -0,0 +1,1481 @@
+//! Built-in health checks.
+//!
+//! This module provides ready-made `DoctorCheck` constructors for common
+//! diagnostics (config files, symlinks, file staleness, commands on `PATH`,
+//! environment variables, external tool versions, network ports and URLs,
+//! config file syntax, git working tree state) so each tool doesn't have to
+//! reimplement them.
+//!
+//! Path-based checks, including `DoctorCheck::file_exists` and `dir_exists`
+//! in the `types` module, expand `~` and environment variables in their
+//! arguments; see [`expand_path`].
+
+use crate::output;
+use crate::types::{DoctorCheck, Severity};
+use regex::Regex;
+use semver::Version;
+use std::ffi::OsStr;
+use std::net::{Ipv4Addr, Ipv6Addr, TcpListener, TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Matches the first `MAJOR.MINOR[.PATCH][-PRERELEASE]` token in a string.
+static VERSION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\d+\.\d+(?:\.\d+)?(?:-[0-9A-Za-z][0-9A-Za-z.-]*)?")
+        .expect("version regex is valid")
+});
+
+/// Expand a leading `~` and `$VAR` / `${VAR}` references in a path.
+///
+/// `~` and `~/...` resolve against the user's home directory. Variables that
+/// are unset (or a `~` when no home directory can be determined) are left as
+/// written. The path-based `DoctorCheck` constructors apply this to their
+/// arguments, so `DoctorCheck::file_exists("~/.config/mytool/config.toml")`
+/// checks the file in the user's home directory.
+///
+/// # Examples
+/// ```
+/// use workhelix_cli_common::expand_path;
+///
+/// let path = expand_path("~/.config/mytool");
+/// assert!(!path.starts_with("~"));
+/// ```
+#[must_use]
+pub fn expand_path(path: &str) -> PathBuf {
+    let path = expand_vars(path);
+    let home_relative = if path == "~" {
+        Some("")
+    } else {
+        path.strip_prefix("~/")
+    };
+    match (home_relative, home::home_dir()) {
+        (Some(""), Some(home)) => home,
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Replace `$VAR` and `${VAR}` with the variable's value, leaving unset ones as written.
+fn expand_vars(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(dollar) = rest.find('$') {
+        out.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        let (name, consumed) = var_reference(after);
+        match std::env::var(name) {
+            Ok(value) if !name.is_empty() => out.push_str(&value),
+            _ => out.push_str(&rest[dollar..=dollar + consumed]),
+        }
+        rest = &after[consumed..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Split the variable name off the text following a `$`, returning the name
+/// and how many bytes of `after` the reference spans (0 if there is none).
+fn var_reference(after: &str) -> (&str, usize) {
+    if let Some(braced) = after.strip_prefix('{') {
+        return braced
+            .find('}')
+            .map_or(("", 0), |end| (&braced[..end], end + 2));
+    }
+    let end = after
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(after.len());
+    (&after[..end], end)
+}
+
+/// Columns a check message is indented by in the doctor report.
+const MESSAGE_INDENT: usize = 5;
+
+/// Fewest columns a path is shortened to, however narrow the terminal.
+const MIN_PATH_WIDTH: usize = 20;
+
+/// `{prefix}{path}`, with the middle of the path elided if the message would
+/// not fit on one line of the terminal.
+pub fn path_message(prefix: &str, path: &Path) -> String {
+    path_message_for_width(prefix, path, output::terminal_width())
+}
+
+/// [`path_message`] for a terminal `width` columns wide.
+fn path_message_for_width(prefix: &str, path: &Path, width: usize) -> String {
+    let budget = width
+        .saturating_sub(MESSAGE_INDENT + prefix.len())
+        .max(MIN_PATH_WIDTH);
+    format!(
+        "{prefix}{}",
+        output::truncate_middle(&path.display().to_string(), budget)
+    )
+}
+
+/// Apply [`expand_path`] to a path given as `&Path`, leaving non-UTF-8 paths untouched.
+pub fn expand(path: &Path) -> PathBuf {
+    path.to_str()
+        .map_or_else(|| path.to_path_buf(), expand_path)
+}
+
+impl DoctorCheck {
+    /// Create a check that a tool's config file exists in the platform's
+    /// config directory; see [`config_path`](crate::config_path).
+    ///
+    /// # Errors
+    /// Returns a failing check if the file doesn't exist.
+    #[must_use]
+    pub fn config_file_exists(tool: &str, file: &str) -> Self {
+        Self::file_exists(crate::config_path(tool, file))
+    }
+
+    /// Create a check that a symlink points to an existing target.
+    ///
+    /// # Errors
+    /// Returns a failing check if the path is missing, is not a symlink, or is
+    /// a dangling symlink; the message names the missing target.
+    pub fn symlink_valid(path: impl AsRef<Path>) -> Self {
+        let path_ref = &expand(path.as_ref());
+        let name = format!("Symlink check: {}", path_ref.display());
+        match std::fs::symlink_metadata(path_ref) {
+            Ok(meta) if meta.file_type().is_symlink() => {}
+            Ok(_) => return Self::fail(name, path_message("Not a symlink: ", path_ref)),
+            Err(_) => return Self::fail(name, path_message("Symlink not found: ", path_ref)),
+        }
+
+        let target = std::fs::read_link(path_ref).unwrap_or_default();
+        // `exists` follows the link, resolving relative targets against its directory
+        if path_ref.exists() {
+            Self::pass(format!(
+                "Symlink valid: {} -> {}",
+                path_ref.display(),
+                target.display()
+            ))
+        } else {
+            Self::fail(
+                name,
+                path_message("Dangling symlink, target not found: ", &target),
+            )
+        }
+    }
+
+    /// Create a check that a generated file is not older than its source.
+    ///
+    /// Passes when `target`'s modification time is at least as new as
+    /// `reference`'s.
+    ///
+    /// # Errors
+    /// Returns a failing check if either file is missing or `target` is stale.
+    pub fn file_newer_than(target: impl AsRef<Path>, reference: impl AsRef<Path>) -> Self {
+        let target = &expand(target.as_ref());
+        let reference = &expand(reference.as_ref());
+        let name = format!("Up to date: {}", target.display());
+        let modified = |path: &Path| std::fs::metadata(path).and_then(|meta| meta.modified());
+
+        let target_time = match modified(target) {
+            Ok(time) => time,
+            Err(e) => {
+                return Self::fail(name, format!("Cannot read {}: {e}", target.display()));
+            }
+        };
+        let reference_time = match modified(reference) {
+            Ok(time) => time,
+            Err(e) => {
+                return Self::fail(name, format!("Cannot read {}: {e}", reference.display()));
+            }
+        };
+
+        if target_time >= reference_time {
+            Self::pass(name)
+        } else {
+            Self::fail(
+                name,
+                format!(
+                    "{} is older than {} (stale, regenerate)",
+                    target.display(),
+                    reference.display()
+                ),
+            )
+        }
+    }
+
+    /// Create a check that a local TCP port is free to bind.
+    ///
+    /// Binds `127.0.0.1:{port}` and, where IPv6 is available, `[::1]:{port}`,
+    /// releasing the sockets immediately.
+    ///
+    /// # Errors
+    /// Returns a failing check if the port is already in use or cannot be
+    /// bound on 127.0.0.1.
+    #[must_use]
+    pub fn port_available(port: u16) -> Self {
+        let name = format!("Port available: {port}");
+        match TcpListener::bind((Ipv4Addr::LOCALHOST, port)) {
+            Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => {
+                return Self::fail(name, format!("Port {port} in use on 127.0.0.1 ({e})"));
+            }
+            Err(e) => {
+                return Self::fail(name, format!("Cannot bind 127.0.0.1:{port}: {e}"));
+            }
+            Ok(_) => {}
+        }
+        match TcpListener::bind((Ipv6Addr::LOCALHOST, port)) {
+            Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => {
+                Self::fail(name, format!("Port {port} in use on [::1] ({e})"))
+            }
+            // No IPv6 loopback on this host is not a conflict
+            _ => Self::pass(name),
+        }
+    }
+
+    /// Create a check that a TCP service is accepting connections.
+    ///
+    /// Every address `host` resolves to is tried, each with `timeout`.
+    ///
+    /// # Errors
+    /// Returns a failing check if the host cannot be resolved or no address
+    /// accepts a connection.
+    #[must_use]
+    pub fn port_reachable(host: &str, port: u16, timeout: Duration) -> Self {
+        let name = format!("Reachable: {host}:{port}");
+        let addrs = match (host, port).to_socket_addrs() {
+            Ok(addrs) => addrs,
+            Err(e) => return Self::fail(name, format!("Cannot resolve {host}: {e}")),
+        };
+
+        let mut last_error = None;
+        for addr in addrs {
+            match TcpStream::connect_timeout(&addr, timeout) {
+                Ok(_) => return Self::pass(name),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        let reason = last_error.map_or_else(|| "no addresses".to_string(), |e| e.to_string());
+        Self::fail(name, format!("Cannot connect to {host}:{port}: {reason}"))
+    }
+
+    /// Create a check that an HTTP(S) URL responds successfully.
+    ///
+    /// Sends a HEAD request (falling back to GET if the server rejects HEAD)
+    /// bounded by `timeout`. Any 2xx or 3xx status passes; redirects are not
+    /// followed. Errors and other statuses are retried up to `retries` more
+    /// times with exponential backoff starting at 100ms, to ride out transient
+    /// network blips.
+    ///
+    /// In offline mode (see [`crate::set_offline`]) no request is made and the
+    /// check is a warning with a "Skipped (offline mode)" message, so the skip
+    /// is visible in the report without failing it.
+    ///
+    /// # Errors
+    /// Returns a failing check with the last status or error once every
+    /// attempt has failed.
+    #[cfg(feature = "http")]
+    #[must_use]
+    pub fn http_reachable(url: &str, retries: u32, timeout: Duration) -> Self {
+        let name = format!("Reachable: {url}");
+        if crate::update::is_offline() {
+            return Self::warn(name, "Skipped (offline mode)");
+        }
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .timeout_global(Some(timeout))
+            .http_status_as_error(false)
+            .max_redirects(0)
+            .max_redirects_will_error(false)
+            .build()
+            .into();
+
+        let attempts = retries.saturating_add(1);
+        let mut backoff = HTTP_RETRY_BACKOFF;
+        let mut last_error = String::new();
+        for attempt in 1..=attempts {
+            match http_status(&agent, url) {
+                Ok(status) if (200..400).contains(&status) => return Self::pass(name),
+                Ok(status) => last_error = format!("HTTP {status}"),
+                Err(e) => last_error = e.to_string(),
+            }
+            if attempt < attempts {
+                std::thread::sleep(backoff);
+                backoff = backoff.saturating_mul(2);
+            }
+        }
+        let plural = if attempts == 1 { "" } else { "s" };
+        Self::fail(
+            name,
+            format!("Unreachable after {attempts} attempt{plural}: {last_error}"),
+        )
+    }
+
+    /// Create a check that the filesystem containing `path` has at least
+    /// `min_bytes` of space available to unprivileged users.
+    ///
+    /// Returns a warning if less than `min_bytes` is available.
+    ///
+    /// # Errors
+    /// Returns a failing check if free space cannot be determined.
+    #[cfg(feature = "disk-space")]
+    pub fn min_free_space(path: impl AsRef<Path>, min_bytes: u64) -> Self {
+        let path_ref = &expand(path.as_ref());
+        let name = format!("Free space: {}", path_ref.display());
+        match fs4::available_space(path_ref) {
+            Ok(available) if available >= min_bytes => Self::pass(name),
+            Ok(available) => Self::warn(
+                name,
+                format!(
+                    "Only {} available, need {}",
+                    output::format_bytes(available),
+                    output::format_bytes(min_bytes)
+                ),
+            ),
+            Err(e) => Self::fail(name, format!("Cannot determine free space: {e}")),
+        }
+    }
+
+    /// Create a check that a TOML config file exists and parses.
+    ///
+    /// # Errors
+    /// Returns a failing check if the file is missing or unreadable, or with
+    /// the parse error and its line and column if it is not valid TOML.
+    #[cfg(feature = "toml")]
+    pub fn toml_valid(path: impl AsRef<Path>) -> Self {
+        let path_ref = &expand(path.as_ref());
+        let name = format!("Valid TOML: {}", path_ref.display());
+        let contents = match read_config(path_ref) {
+            Ok(contents) => contents,
+            Err(message) => return Self::fail(name, message),
+        };
+        match contents.parse::<toml::Table>() {
+            Ok(_) => Self::pass(name),
+            Err(e) => {
+                let message = e.message().trim_end();
+                let detail = e.span().map_or_else(
+                    || message.to_string(),
+                    |span| {
+                        let (line, column) = line_column(&contents, span.start);
+                        format!("{message} at line {line} column {column}")
+                    },
+                );
+                Self::fail(name, format!("Parse error: {detail}"))
+            }
+        }
+    }
+
+    /// Create a check that a JSON config file exists and parses.
+    ///
+    /// # Errors
+    /// Returns a failing check if the file is missing or unreadable, or with
+    /// the parse error and its line and column if it is not valid JSON.
+    #[cfg(feature = "json")]
+    pub fn json_valid(path: impl AsRef<Path>) -> Self {
+        let path_ref = &expand(path.as_ref());
+        let name = format!("Valid JSON: {}", path_ref.display());
+        let contents = match read_config(path_ref) {
+            Ok(contents) => contents,
+            Err(message) => return Self::fail(name, message),
+        };
+        match serde_json::from_str::<serde_json::Value>(&contents) {
+            Ok(_) => Self::pass(name),
+            // serde_json's message already ends with "at line L column C"
+            Err(e) => Self::fail(name, format!("Parse error: {e}")),
+        }
+    }
+
+    /// Create a check that a path is writable.
+    ///
+    /// For a directory, a temporary file is created inside it and removed again.
+    /// For a file, it is opened for writing without modifying its contents.
+    ///
+    /// # Errors
+    /// Returns a failing check if the path does not exist or cannot be written,
+    /// with the message distinguishing the two.
+    pub fn writable(path: impl AsRef<Path>) -> Self {
+        let path_ref = &expand(path.as_ref());
+        let result = if path_ref.is_dir() {
+            let probe = path_ref.join(format!(".doctor-write-test-{}", std::process::id()));
+            std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&probe)
+                .and_then(|_| std::fs::remove_file(&probe))
+        } else {
+            std::fs::OpenOptions::new()
+                .write(true)
+                .open(path_ref)
+                .map(|_| ())
+        };
+
+        match result {
+            Ok(()) => Self::pass(format!("Writable: {}", path_ref.display())),
+            Err(e) => {
+                let reason = match e.kind() {
+                    std::io::ErrorKind::NotFound => "does not exist".to_string(),
+                    std::io::ErrorKind::PermissionDenied => "permission denied".to_string(),
+                    _ => e.to_string(),
+                };
+                Self::fail(
+                    format!("Write check: {}", path_ref.display()),
+                    path_message(&format!("Not writable ({reason}): "), path_ref),
+                )
+            }
+        }
+    }
+
+    /// Create a check that an executable is available on `PATH`.
+    ///
+    /// The command is resolved, not executed. On Windows, `PATHEXT` extensions
+    /// (e.g. `.exe`, `.cmd`) are tried when `cmd` has no extension. A `cmd`
+    /// containing a path separator is checked directly instead of searched for.
+    /// On success the resolved path is stored in the check's message.
+    ///
+    /// # Errors
+    /// Returns a failing check if the command cannot be found.
+    #[must_use]
+    pub fn command_exists(cmd: &str) -> Self {
+        let path_var = std::env::var_os("PATH").unwrap_or_default();
+        let pathext = std::env::var_os("PATHEXT");
+        find_executable(cmd, &path_var, pathext.as_deref()).map_or_else(
+            || {
+                Self::fail(
+                    format!("Command check: {cmd}"),
+                    format!("{cmd} not found in PATH"),
+                )
+            },
+            |resolved| Self {
+                message: Some(resolved.display().to_string()),
+                ..Self::pass(format!("Command available: {cmd}"))
+            },
+        )
+    }
+
+    /// Create a check that an environment variable is set and non-empty.
+    ///
+    /// # Errors
+    /// Returns a failing check if the variable is unset or empty.
+    #[must_use]
+    pub fn env_var(name: &str) -> Self {
+        match std::env::var_os(name) {
+            Some(value) if !value.is_empty() => {
+                Self::pass(format!("Environment variable set: {name}"))
+            }
+            Some(_) => Self::fail(
+                format!("Environment variable check: {name}"),
+                format!("{name} is set but empty"),
+            ),
+            None => Self::fail(
+                format!("Environment variable check: {name}"),
+                format!("{name} is not set"),
+            ),
+        }
+    }
+
+    /// Create a check that an environment variable is set and its value satisfies `predicate`.
+    ///
+    /// # Examples
+    /// ```
+    /// use workhelix_cli_common::DoctorCheck;
+    ///
+    /// let check = DoctorCheck::env_var_matches("MYTOOL_API_URL", |v| v.starts_with("https://"));
+    /// # let _ = check;
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a failing check if the variable is unset, not valid UTF-8, or
+    /// rejected by `predicate`.
+    pub fn env_var_matches(name: &str, predicate: impl Fn(&str) -> bool) -> Self {
+        let fail =
+            |message: String| Self::fail(format!("Environment variable check: {name}"), message);
+        match std::env::var(name) {
+            Ok(value) if predicate(&value) => {
+                Self::pass(format!("Environment variable valid: {name}"))
+            }
+            Ok(_) => fail(format!("{name} does not have the expected format")),
+            Err(std::env::VarError::NotPresent) => fail(format!("{name} is not set")),
+            Err(std::env::VarError::NotUnicode(_)) => fail(format!("{name} is not valid UTF-8")),
+        }
+    }
+
+    /// Create a check that an external command reports at least version `min`.
+    ///
+    /// Runs `cmd` with `args` (e.g. `git --version`), takes the first
+    /// version-looking token from its stdout or stderr, and compares it to `min`
+    /// using semver ordering. Two-part versions like `2.30` are treated as `2.30.0`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use workhelix_cli_common::DoctorCheck;
+    ///
+    /// let check = DoctorCheck::version_at_least("git", &["--version"], "2.30");
+    /// # let _ = check;
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a failing check if the command is missing, its version can't be
+    /// determined, or it is older than `min`.
+    #[must_use]
+    pub fn version_at_least(cmd: &str, args: &[&str], min: &str) -> Self {
+        let name = format!("Version check: {cmd} >= {min}");
+        let Some(required) = extract_version(min) else {
+            return Self::fail(name, format!("Invalid minimum version: {min}"));
+        };
+
+        let output = match Command::new(cmd).args(args).output() {
+            Ok(output) => output,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Self::fail(name, format!("{cmd} not found in PATH"));
+            }
+            Err(e) => return Self::fail(name, format!("Failed to run {cmd}: {e}")),
+        };
+
+        let text = format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        match extract_version(&text) {
+            Some(found) if found >= required => Self {
+                message: Some(format!("{cmd} {found}")),
+                ..Self::pass(name)
+            },
+            Some(found) => Self::fail(
+                name,
+                format!("{cmd} {found} is older than required {required}"),
+            ),
+            None => Self::fail(
+                name,
+                format!("Could not determine {cmd} version from its output"),
+            ),
+        }
+    }
+
+    /// Create a check that a git working tree has no uncommitted changes.
+    ///
+    /// Runs `git status --porcelain` in `path`; untracked files count as
+    /// changes, ignored files do not.
+    ///
+    /// # Errors
+    /// Returns a warning naming the changed paths if the tree is dirty, and a
+    /// failing check if git is missing or `path` is not inside a git repository.
+    pub fn git_repo_clean(path: impl AsRef<Path>) -> Self {
+        let path_ref = &expand(path.as_ref());
+        let name = format!("Git working tree clean: {}", path_ref.display());
+        let output = match Command::new("git")
+            .arg("-C")
+            .arg(path_ref)
+            .args(["status", "--porcelain=v1", "-z"])
+            .env("GIT_OPTIONAL_LOCKS", "0")
+            .output()
+        {
+            Ok(output) => output,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Self::fail(name, "git not found in PATH");
+            }
+            Err(e) => return Self::fail(name, format!("Failed to run git: {e}")),
+        };
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = if stderr.to_lowercase().contains("not a git repository") {
+                path_message("Not a git repository: ", path_ref)
+            } else {
+                format!("git status failed: {}", stderr.trim())
+            };
+            return Self::fail(name, message);
+        }
+
+        let changed = porcelain_paths(&output.stdout);
+        match changed.as_slice() {
+            [] => Self::pass(name),
+            [path] => Self::warn(name, format!("Uncommitted change: {path}")),
+            [first, second] => {
+                Self::warn(name, format!("2 uncommitted changes: {first}, {second}"))
+            }
+            [first, second, rest @ ..] => Self::warn(
+                name,
+                format!(
+                    "{} uncommitted changes: {first}, {second} and {} more",
+                    changed.len(),
+                    rest.len()
+                ),
+            ),
+        }
+    }
+
+    /// Combine checks into one that passes if at least one of them passes.
+    ///
+    /// Useful when a condition can be met in several ways, such as a config
+    /// file in any of several locations. If none passes, the result is a
+    /// warning when some inner check only warned, and a failure otherwise; its
+    /// message lists every inner check's message.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use workhelix_cli_common::DoctorCheck;
+    ///
+    /// let check = DoctorCheck::any_of(
+    ///     "Config file",
+    ///     vec![
+    ///         DoctorCheck::file_exists("~/.config/mytool/config.toml"),
+    ///         DoctorCheck::file_exists("/etc/mytool/config.toml"),
+    ///     ],
+    /// );
+    /// # let _ = check;
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a failing check if no inner check passes or warns, including
+    /// when `checks` is empty.
+    #[must_use]
+    pub fn any_of(name: &str, checks: impl IntoIterator<Item = Self>) -> Self {
+        let checks: Vec<Self> = checks.into_iter().collect();
+        if checks.iter().any(|check| check.severity == Severity::Pass) {
+            return Self::pass(name);
+        }
+        let message = combined_messages(&checks, |_| true);
+        if checks.iter().any(|check| check.severity == Severity::Warn) {
+            Self::warn(name, message)
+        } else if checks.is_empty() {
+            Self::fail(name, "No checks to satisfy")
+        } else {
+            Self::fail(name, message)
+        }
+    }
+
+    /// Combine checks into one that passes only if all of them pass.
+    ///
+    /// The result takes the most severe inner result; its message lists the
+    /// messages of the inner checks with that severity.
+    ///
+    /// # Errors
+    /// Returns a failing check if any inner check fails.
+    #[must_use]
+    pub fn all_of(name: &str, checks: impl IntoIterator<Item = Self>) -> Self {
+        let checks: Vec<Self> = checks.into_iter().collect();
+        let worst = checks
+            .iter()
+            .map(|check| check.severity)
+            .max()
+            .unwrap_or(Severity::Pass);
+        let message = combined_messages(&checks, |check| check.severity == worst);
+        match worst {
+            Severity::Pass => Self::pass(name),
+            Severity::Warn => Self::warn(name, message),
+            Severity::Fail => Self::fail(name, message),
+        }
+    }
+}
+
+/// Join `name: message` for the non-passing checks selected by `filter`.
+fn combined_messages(checks: &[DoctorCheck], filter: impl Fn(&DoctorCheck) -> bool) -> String {
+    checks
+        .iter()
+        .filter(|check| check.severity != Severity::Pass && filter(check))
+        .map(|check| {
+            check.message.as_ref().map_or_else(
+                || check.name.clone(),
+                |message| format!("{}: {message}", check.name),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Extract the first semver-looking version from free-form text.
+///
+/// Missing patch components default to zero, so `"git version 2.30"` yields `2.30.0`.
+fn extract_version(text: &str) -> Option<Version> {
+    crate::version::parse_version(VERSION_RE.find(text)?.as_str())
+}
+
+/// Resolve `cmd` against a `PATH`-style search list.
+///
+/// `pathext` is only consulted on Windows, mirroring how the shell resolves
+/// commands there.
+fn find_executable(cmd: &str, path_var: &OsStr, pathext: Option<&OsStr>) -> Option<PathBuf> {
+    let candidates = candidate_names(cmd, pathext);
+
+    if cmd.contains(['/', std::path::MAIN_SEPARATOR]) {
+        return candidates
+            .into_iter()
+            .map(PathBuf::from)
+            .find(|path| is_executable(path));
+    }
+
+    std::env::split_paths(path_var)
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .flat_map(|dir| candidates.iter().map(move |name| dir.join(name)))
+        .find(|path| is_executable(path))
+}
+
+fn candidate_names(cmd: &str, pathext: Option<&OsStr>) -> Vec<String> {
+    let mut names = vec![cmd.to_string()];
+    if cfg!(windows) && Path::new(cmd).extension().is_none() {
+        let pathext = pathext
+            .and_then(OsStr::to_str)
+            .unwrap_or(".COM;.EXE;.BAT;.CMD");
+        names.extend(
+            pathext
+                .split(';')
+                .filter(|ext| !ext.is_empty())
+                .map(|ext| format!("{cmd}{}", ext.to_lowercase())),
+        );
+    }
+    names
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Delay before the first retry of [`DoctorCheck::http_reachable`]; doubles after each attempt.
+#[cfg(feature = "http")]
+const HTTP_RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Status code of a HEAD request to `url`, retried as GET if HEAD is not allowed.
+#[cfg(feature = "http")]
+fn http_status(agent: &ureq::Agent, url: &str) -> Result<u16, ureq::Error> {
+    let status = agent.head(url).call()?.status().as_u16();
+    if status == 405 || status == 501 {
+        return Ok(agent.get(url).call()?.status().as_u16());
+    }
+    Ok(status)
+}
+
+/// Paths listed by `git status --porcelain=v1 -z`.
+///
+/// Entries are NUL-separated `XY path` records; a rename or copy is followed
+/// by an extra record holding the original path, which is skipped.
+fn porcelain_paths(stdout: &[u8]) -> Vec<String> {
+    let text = String::from_utf8_lossy(stdout);
+    let mut records = text.split('\0').filter(|record| !record.is_empty());
+    let mut paths = Vec::new();
+    while let Some(record) = records.next() {
+        let Some((status, path)) = record.split_at_checked(2) else {
+            continue;
+        };
+        paths.push(path.strip_prefix(' ').unwrap_or(path).to_string());
+        if status.contains(['R', 'C']) {
+            records.next();
+        }
+    }
+    paths
+}
+
+/// Read a config file, distinguishing a missing file from other read errors.
+#[cfg(any(feature = "toml", feature = "json"))]
+fn read_config(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            path_message("File not found: ", path)
+        } else {
+            format!("Cannot read {}: {e}", path.display())
+        }
+    })
+}
+
+/// 1-based line and column of the byte `offset` in `text`.
+#[cfg(feature = "toml")]
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map_or(0, |current| current.chars().count())
+        + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_env::{with_var, with_vars};
+
+    #[test]
+    fn test_config_file_exists() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().to_str().unwrap();
+        let vars = [
+            ("XDG_CONFIG_HOME", Some(base)),
+            ("APPDATA", Some(base)),
+            ("HOME", Some(base)),
+        ];
+        with_vars(&vars, || {
+            let path = crate::config_path("mytool", "config.toml");
+            assert!(path.starts_with(dir.path()));
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, "").unwrap();
+            assert!(DoctorCheck::config_file_exists("mytool", "config.toml").passed);
+            assert!(!DoctorCheck::config_file_exists("mytool", "missing.toml").passed);
+        });
+    }
+
+    #[test]
+    fn test_long_path_message_is_shortened() {
+        let long = format!("/tmp/{}/config.toml", "nested/".repeat(40));
+        let message = path_message_for_width("File not found: ", Path::new(&long), 80);
+        assert!(message.starts_with("File not found: /tmp/"));
+        assert!(message.ends_with("/config.toml"));
+        assert!(message.contains('…'));
+        assert_eq!(message.chars().count(), 80 - MESSAGE_INDENT);
+        assert_eq!(
+            path_message_for_width("File not found: ", Path::new(&long), 10),
+            format!(
+                "File not found: {}",
+                output::truncate_middle(&long, MIN_PATH_WIDTH)
+            )
+        );
+
+        // Wider than any terminal, so file_exists shortens it whatever the width
+        let long = format!("/tmp/{}/config.toml", "nested/".repeat(2000));
+        let check = DoctorCheck::file_exists(&long);
+        let message = check.message.unwrap();
+        assert!(message.contains('…'));
+        assert!(message.chars().count() < long.len());
+        // The check name keeps the full path
+        assert!(check.name.ends_with(&long));
+
+        let check = DoctorCheck::dir_exists("/nonexistent/short");
+        assert_eq!(
+            check.message.as_deref(),
+            Some("Directory not found: /nonexistent/short")
+        );
+    }
+
+    #[test]
+    fn test_any_of() {
+        let check = DoctorCheck::any_of(
+            "Config",
+            vec![
+                DoctorCheck::fail("home", "missing"),
+                DoctorCheck::pass("etc"),
+            ],
+        );
+        assert_eq!(check.severity, Severity::Pass);
+        assert_eq!(check.name, "Config");
+
+        let check = DoctorCheck::any_of(
+            "Config",
+            vec![
+                DoctorCheck::fail("home", "missing"),
+                DoctorCheck::fail("etc", "unreadable"),
+            ],
+        );
+        assert_eq!(check.severity, Severity::Fail);
+        assert_eq!(
+            check.message.as_deref(),
+            Some("home: missing; etc: unreadable")
+        );
+
+        let check = DoctorCheck::any_of(
+            "Config",
+            vec![
+                DoctorCheck::fail("home", "missing"),
+                DoctorCheck::warn("etc", "world-readable"),
+            ],
+        );
+        assert_eq!(check.severity, Severity::Warn);
+        assert_eq!(
+            DoctorCheck::any_of("Config", Vec::new()).severity,
+            Severity::Fail
+        );
+    }
+
+    #[test]
+    fn test_all_of() {
+        let check = DoctorCheck::all_of(
+            "Toolchain",
+            vec![DoctorCheck::pass("cargo"), DoctorCheck::pass("rustc")],
+        );
+        assert_eq!(check.severity, Severity::Pass);
+
+        let check = DoctorCheck::all_of(
+            "Toolchain",
+            vec![
+                DoctorCheck::pass("cargo"),
+                DoctorCheck::fail("rustc", "not found"),
+                DoctorCheck::warn("rustfmt", "outdated"),
+                DoctorCheck::fail("clippy", "not found"),
+            ],
+        );
+        assert_eq!(check.severity, Severity::Fail);
+        assert_eq!(
+            check.message.as_deref(),
+            Some("rustc: not found; clippy: not found")
+        );
+
+        let check = DoctorCheck::all_of(
+            "Toolchain",
+            vec![
+                DoctorCheck::pass("cargo"),
+                DoctorCheck::warn("rustfmt", "outdated"),
+            ],
+        );
+        assert_eq!(check.severity, Severity::Warn);
+        assert_eq!(check.message.as_deref(), Some("rustfmt: outdated"));
+        assert!(DoctorCheck::all_of("Toolchain", Vec::new()).passed);
+    }
+
+    fn make_executable(dir: &Path, name: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, "#!/bin/sh\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn test_expand_path() {
+        with_vars(
+            &[("HOME", Some("/home/tester")), ("DOCTOR_TEST_UNSET", None)],
+            || {
+                assert_eq!(expand_path("~/x"), PathBuf::from("/home/tester/x"));
+                assert_eq!(expand_path("~"), PathBuf::from("/home/tester"));
+                assert_eq!(expand_path("$HOME/x"), PathBuf::from("/home/tester/x"));
+                assert_eq!(expand_path("${HOME}/x"), PathBuf::from("/home/tester/x"));
+                assert_eq!(expand_path("/etc/~x"), PathBuf::from("/etc/~x"));
+                assert_eq!(
+                    expand_path("$DOCTOR_TEST_UNSET/x"),
+                    PathBuf::from("$DOCTOR_TEST_UNSET/x")
+                );
+                assert_eq!(expand_path("cost$"), PathBuf::from("cost$"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_file_exists_expands_home() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("config.toml"), "").unwrap();
+        with_var("HOME", dir.path().to_str(), || {
+            assert!(DoctorCheck::file_exists("~/config.toml").passed);
+            assert!(DoctorCheck::dir_exists("$HOME").passed);
+        });
+    }
+
+    fn set_mtime(path: &Path, time: std::time::SystemTime) {
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_file_newer_than() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("schema.json");
+        let generated = dir.path().join("schema.rs");
+        std::fs::write(&source, "{}").unwrap();
+        std::fs::write(&generated, "// generated").unwrap();
+
+        let now = std::time::SystemTime::now();
+        let hour = std::time::Duration::from_secs(3600);
+        set_mtime(&source, now - hour);
+        set_mtime(&generated, now);
+        assert!(DoctorCheck::file_newer_than(&generated, &source).passed);
+
+        set_mtime(&generated, now - hour);
+        assert!(DoctorCheck::file_newer_than(&generated, &source).passed);
+
+        set_mtime(&generated, now - hour * 2);
+        let check = DoctorCheck::file_newer_than(&generated, &source);
+        assert!(!check.passed);
+        assert!(check.message.unwrap().contains("stale, regenerate"));
+    }
+
+    #[test]
+    fn test_file_newer_than_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("exists");
+        std::fs::write(&existing, "").unwrap();
+        let missing = dir.path().join("missing");
+
+        let check = DoctorCheck::file_newer_than(&missing, &existing);
+        assert!(!check.passed);
+        assert!(check.message.unwrap().contains("missing"));
+
+        let check = DoctorCheck::file_newer_than(&existing, &missing);
+        assert!(!check.passed);
+        assert!(check.message.unwrap().contains("missing"));
+    }
+
+    #[test]
+    fn test_port_available() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let check = DoctorCheck::port_available(port);
+        assert!(!check.passed);
+        assert!(check.message.unwrap().contains("in use"));
+
+        drop(listener);
+        assert!(DoctorCheck::port_available(port).passed);
+    }
+
+    #[test]
+    fn test_port_reachable() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let timeout = Duration::from_secs(1);
+
+        assert!(DoctorCheck::port_reachable("127.0.0.1", port, timeout).passed);
+
+        drop(listener);
+        let check = DoctorCheck::port_reachable("127.0.0.1", port, timeout);
+        assert!(!check.passed);
+        assert!(check.message.unwrap().contains("Cannot connect"));
+    }
+
+    /// Serve `statuses` in order, one response per request, then stop.
+    /// Returns the server's URL.
+    #[cfg(feature = "http")]
+    fn mock_http_server(statuses: Vec<u16>) -> String {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let url = format!("http://{}/health", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for status in statuses {
+                let Ok((stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut reader = BufReader::new(stream);
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok_and(|n| n > 0) && line != "\r\n" {
+                    line.clear();
+                }
+                let response = format!(
+                    "HTTP/1.1 {status} Mock\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                );
+                let _ = reader.get_mut().write_all(response.as_bytes());
+            }
+        });
+        url
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_http_reachable_success_after_retry() {
+        let _guard = crate::test_env::offline_lock();
+        let url = mock_http_server(vec![503, 200]);
+        let check = DoctorCheck::http_reachable(&url, 2, Duration::from_secs(5));
+        assert!(check.passed, "{:?}", check.message);
+
+        let url = mock_http_server(vec![301]);
+        assert!(DoctorCheck::http_reachable(&url, 0, Duration::from_secs(5)).passed);
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_http_reachable_skipped_offline() {
+        let _guard = crate::test_env::offline_lock();
+        crate::set_offline(true);
+        // Nothing listens on the discard port, so a real request would fail
+        let check = DoctorCheck::http_reachable("http://127.0.0.1:9", 3, Duration::from_secs(5));
+        crate::set_offline(false);
+        assert!(check.passed);
+        assert_eq!(check.severity, Severity::Warn);
+        assert_eq!(check.message.as_deref(), Some("Skipped (offline mode)"));
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_http_reachable_offline_skip_is_rendered() {
+        struct OfflineTool;
+
+        impl crate::DoctorChecks for OfflineTool {
+            fn repo_info() -> crate::RepoInfo {
+                crate::RepoInfo::new("workhelix", "offline-tool", "v")
+            }
+
+            fn current_version() -> &'static str {
+                "1.0.0"
+            }
+
+            fn tool_checks(&self) -> Vec<DoctorCheck> {
+                vec![DoctorCheck::http_reachable(
+                    "http://127.0.0.1:9",
+                    0,
+                    Duration::from_secs(5),
+                )]
+            }
+        }
+
+        let _guard = crate::test_env::offline_lock();
+        crate::set_offline(true);
+        let mut buffer = Vec::new();
+        let exit_code = crate::doctor::run_doctor_to(&OfflineTool, &mut buffer);
+        crate::set_offline(false);
+
+        let text = String::from_utf8(buffer).unwrap();
+        assert_eq!(exit_code, 0);
+        assert!(text.contains("Reachable: http://127.0.0.1:9\n     Skipped (offline mode)\n"));
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_http_reachable_all_retries_fail() {
+        let _guard = crate::test_env::offline_lock();
+        let url = mock_http_server(vec![500, 500, 500]);
+        let check = DoctorCheck::http_reachable(&url, 2, Duration::from_secs(5));
+        assert!(!check.passed);
+        assert_eq!(
+            check.message.as_deref(),
+            Some("Unreachable after 3 attempts: HTTP 500")
+        );
+
+        // Nothing is listening any more once the server thread has finished
+        let check = DoctorCheck::http_reachable(&url, 0, Duration::from_secs(5));
+        assert!(!check.passed);
+        assert!(
+            check
+                .message
+                .unwrap()
+                .starts_with("Unreachable after 1 attempt: ")
+        );
+    }
+
+    #[cfg(feature = "disk-space")]
+    #[test]
+    fn test_min_free_space() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(DoctorCheck::min_free_space(dir.path(), 1).passed);
+
+        let check = DoctorCheck::min_free_space(dir.path(), u64::MAX);
+        assert!(check.passed);
+        assert_eq!(check.severity, Severity::Warn);
+        assert!(check.message.unwrap().contains("need 18.4 EB"));
+
+        let check = DoctorCheck::min_free_space(dir.path().join("missing"), 1);
+        assert!(!check.passed);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_valid() {
+        let dir = tempfile::tempdir().unwrap();
+        let valid = dir.path().join("valid.toml");
+        std::fs::write(&valid, "[server]\nport = 8080\n").unwrap();
+        assert!(DoctorCheck::toml_valid(&valid).passed);
+
+        let broken = dir.path().join("broken.toml");
+        std::fs::write(&broken, "[server]\nport = = 8080\n").unwrap();
+        let check = DoctorCheck::toml_valid(&broken);
+        assert!(!check.passed);
+        let message = check.message.unwrap();
+        assert!(message.starts_with("Parse error:"), "{message}");
+        assert!(message.contains("at line 2 column"), "{message}");
+
+        let check = DoctorCheck::toml_valid(dir.path().join("missing.toml"));
+        assert!(!check.passed);
+        assert!(check.message.unwrap().starts_with("File not found:"));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_valid() {
+        let dir = tempfile::tempdir().unwrap();
+        let valid = dir.path().join("valid.json");
+        std::fs::write(&valid, r#"{"port": 8080}"#).unwrap();
+        assert!(DoctorCheck::json_valid(&valid).passed);
+
+        let broken = dir.path().join("broken.json");
+        std::fs::write(&broken, "{\n  \"port\": ,\n}").unwrap();
+        let check = DoctorCheck::json_valid(&broken);
+        assert!(!check.passed);
+        let message = check.message.unwrap();
+        assert!(message.starts_with("Parse error:"), "{message}");
+        assert!(message.contains("line 2 column"), "{message}");
+
+        let check = DoctorCheck::json_valid(dir.path().join("missing.json"));
+        assert!(!check.passed);
+        assert!(check.message.unwrap().starts_with("File not found:"));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_line_column() {
+        assert_eq!(line_column("abc", 0), (1, 1));
+        assert_eq!(line_column("ab\ncd", 4), (2, 2));
+    }
+
+    #[test]
+    fn test_writable() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("data.db");
+        std::fs::write(&file, "contents").unwrap();
+
+        assert!(DoctorCheck::writable(dir.path()).passed);
+        assert!(DoctorCheck::writable(&file).passed);
+        // The probe file is cleaned up and the file left untouched
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "contents");
+    }
+
+    #[test]
+    fn test_writable_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let check = DoctorCheck::writable(dir.path().join("missing"));
+        assert!(!check.passed);
+        assert!(check.message.unwrap().contains("does not exist"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_valid() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("config.toml"), "").unwrap();
+        let valid = dir.path().join("valid");
+        let dangling = dir.path().join("dangling");
+        std::os::unix::fs::symlink("config.toml", &valid).unwrap();
+        std::os::unix::fs::symlink(dir.path().join("gone.toml"), &dangling).unwrap();
+
+        let check = DoctorCheck::symlink_valid(&valid);
+        assert!(check.passed);
+        assert!(check.name.ends_with("valid -> config.toml"));
+
+        let check = DoctorCheck::symlink_valid(&dangling);
+        assert!(!check.passed);
+        let message = check.message.unwrap();
+        assert!(message.starts_with("Dangling symlink"));
+        assert!(message.ends_with("gone.toml"));
+    }
+
+    #[test]
+    fn test_symlink_valid_not_a_symlink() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("plain");
+        std::fs::write(&file, "").unwrap();
+
+        let check = DoctorCheck::symlink_valid(&file);
+        assert!(!check.passed);
+        assert!(check.message.unwrap().starts_with("Not a symlink: "));
+
+        let check = DoctorCheck::symlink_valid(dir.path().join("missing"));
+        assert!(!check.passed);
+        assert!(check.message.unwrap().starts_with("Symlink not found: "));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_writable_read_only_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let locked = dir.path().join("locked");
+        std::fs::create_dir(&locked).unwrap();
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o555)).unwrap();
+
+        // Privileged users (e.g. root in CI containers) bypass permission bits.
+        let enforced = std::fs::write(locked.join("probe"), "").is_err();
+        let check = DoctorCheck::writable(&locked);
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        if enforced {
+            assert!(!check.passed);
+            assert!(check.message.unwrap().contains("permission denied"));
+        }
+    }
+
+    #[test]
+    fn test_find_executable_in_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let expected = make_executable(dir.path(), "mytool-helper");
+        let path_var = std::env::join_paths([dir.path()]).unwrap();
+
+        let found = find_executable("mytool-helper", &path_var, None);
+        assert_eq!(found, Some(expected));
+        assert_eq!(find_executable("not-there", &path_var, None), None);
+    }
+
+    #[test]
+    fn test_find_executable_direct_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let expected = make_executable(dir.path(), "direct");
+        let cmd = expected.to_str().unwrap();
+
+        assert_eq!(find_executable(cmd, OsStr::new(""), None), Some(expected));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_executable_skips_non_executable() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("plain"), "").unwrap();
+        let path_var = std::env::join_paths([dir.path()]).unwrap();
+
+        assert_eq!(find_executable("plain", &path_var, None), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_exists_sh() {
+        let check = DoctorCheck::command_exists("sh");
+        assert!(check.passed);
+        assert!(check.message.unwrap().ends_with("sh"));
+    }
+
+    #[test]
+    fn test_env_var() {
+        const VAR: &str = "WH_TEST_CHECKS_ENV_VAR";
+
+        crate::test_env::with_var(VAR, Some("value"), || {
+            assert!(DoctorCheck::env_var(VAR).passed);
+        });
+        crate::test_env::with_var(VAR, Some(""), || {
+            let check = DoctorCheck::env_var(VAR);
+            assert!(!check.passed);
+            assert!(check.message.unwrap().contains("empty"));
+        });
+        crate::test_env::with_var(VAR, None, || {
+            let check = DoctorCheck::env_var(VAR);
+            assert!(!check.passed);
+            assert!(check.message.unwrap().contains("not set"));
+        });
+    }
+
+    #[test]
+    fn test_env_var_matches() {
+        const VAR: &str = "WH_TEST_CHECKS_ENV_VAR_MATCHES";
+        let is_https = |v: &str| v.starts_with("https://");
+
+        crate::test_env::with_var(VAR, Some("https://example.com"), || {
+            assert!(DoctorCheck::env_var_matches(VAR, is_https).passed);
+        });
+        crate::test_env::with_var(VAR, Some("http://example.com"), || {
+            let check = DoctorCheck::env_var_matches(VAR, is_https);
+            assert!(!check.passed);
+            assert_eq!(
+                check.message.as_deref(),
+                Some("WH_TEST_CHECKS_ENV_VAR_MATCHES does not have the expected format")
+            );
+        });
+        crate::test_env::with_var(VAR, None, || {
+            assert!(!DoctorCheck::env_var_matches(VAR, is_https).passed);
+        });
+    }
+
+    #[test]
+    fn test_extract_version() {
+        assert_eq!(
+            extract_version("git version 2.39.2 (Apple Git-143)"),
+            Some(Version::new(2, 39, 2))
+        );
+        assert_eq!(
+            extract_version("git version 2.42.0.windows.1"),
+            Some(Version::new(2, 42, 0))
+        );
+        assert_eq!(extract_version("tool 2.30"), Some(Version::new(2, 30, 0)));
+        assert_eq!(
+            extract_version("v1.0.0-rc1"),
+            Some(Version::parse("1.0.0-rc1").unwrap())
+        );
+        assert_eq!(extract_version("no version here"), None);
+    }
+
+    #[test]
+    fn test_extract_version_ordering() {
+        let min = extract_version("2.30").unwrap();
+        assert!(extract_version("git version 2.39.2").unwrap() >= min);
+        assert!(extract_version("git version 2.9.5").unwrap() < min);
+        assert!(extract_version("1.0.0-rc1").unwrap() < extract_version("1.0.0").unwrap());
+    }
+
+    #[test]
+    fn test_version_at_least_missing_command() {
+        let check = DoctorCheck::version_at_least("definitely-not-a-real-command-xyz", &[], "1.0");
+        assert!(!check.passed);
+        assert!(check.message.unwrap().contains("not found"));
+    }
+
+    #[test]
+    fn test_version_at_least_invalid_min() {
+        let check = DoctorCheck::version_at_least("sh", &[], "latest");
+        assert!(!check.passed);
+        assert!(check.message.unwrap().contains("Invalid minimum version"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_version_at_least_runs_command() {
+        let check = DoctorCheck::version_at_least("sh", &["-c", "echo tool 2.5.1"], "2.3");
+        assert!(check.passed);
+        assert_eq!(check.message.as_deref(), Some("sh 2.5.1"));
+
+        let check = DoctorCheck::version_at_least("sh", &["-c", "echo tool 2.1.0 >&2"], "2.3");
+        assert!(!check.passed);
+        assert!(check.message.unwrap().contains("older than required 2.3.0"));
+    }
+
+    /// Whether git can be run, so git-based tests can be skipped without it.
+    fn git_available() -> bool {
+        Command::new("git")
+            .arg("--version")
+            .output()
+            .is_ok_and(|output| output.status.success())
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn test_git_repo_clean() {
+        if !git_available() {
+            eprintln!("skipping test_git_repo_clean: git is not installed");
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let check = DoctorCheck::git_repo_clean(dir.path());
+        assert_eq!(check.severity, Severity::Fail);
+        assert!(check.message.unwrap().starts_with("Not a git repository"));
+
+        git(dir.path(), &["init", "-q"]);
+        let check = DoctorCheck::git_repo_clean(dir.path());
+        assert_eq!(check.severity, Severity::Pass, "{:?}", check.message);
+
+        std::fs::write(dir.path().join("dirty file.txt"), "changes").unwrap();
+        let check = DoctorCheck::git_repo_clean(dir.path());
+        assert_eq!(check.severity, Severity::Warn);
+        assert!(check.passed);
+        assert_eq!(
+            check.message.as_deref(),
+            Some("Uncommitted change: dirty file.txt")
+        );
+    }
+
+    #[test]
+    fn test_porcelain_paths() {
+        let stdout = b" M src/lib.rs\0R  new name.rs\0old name.rs\0?? notes.txt\0";
+        assert_eq!(
+            porcelain_paths(stdout),
+            ["src/lib.rs", "new name.rs", "notes.txt"]
+        );
+        assert!(porcelain_paths(b"").is_empty());
+        // Only the separator is stripped, not spaces that belong to the name
+        assert_eq!(porcelain_paths(b"??  leading.txt\0"), [" leading.txt"]);
+    }
+
+    #[test]
+    fn test_command_exists_missing() {
+        let check = DoctorCheck::command_exists("definitely-not-a-real-command-xyz");
+        assert_eq!(check.severity, Severity::Fail);
+        assert!(check.message.unwrap().contains("not found in PATH"));
+    }
+}
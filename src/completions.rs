@@ -3,9 +3,12 @@
 //! This module provides generic shell completion generation for CLI tools using clap.
 //! It works with any clap `CommandFactory` and generates completions for all major shells.
 
+use crate::output;
+use crate::types::{CommonError, ExitCode};
 use clap::CommandFactory;
-use clap_complete::Shell;
-use std::io;
+use clap_complete::{Generator, Shell};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
 /// Generate shell completion scripts for a clap-based CLI.
 ///
@@ -31,39 +34,317 @@ use std::io;
 /// generate_completions::<Cli>(clap_complete::Shell::Bash);
 /// ```
 pub fn generate_completions<T: CommandFactory>(shell: Shell) {
+    let bin_name = T::command().get_name().to_string();
+    generate_completions_named::<T>(shell, &bin_name);
+}
+
+/// Generate shell completion scripts using an explicit binary name.
+///
+/// Behaves like [`generate_completions`], but uses `bin_name` in both the
+/// installation instructions and the generated script. This is useful for tools
+/// invoked through a wrapper (e.g. `cargo mytool`) whose command name differs
+/// from the name users type.
+///
+/// # Examples
+/// ```no_run
+/// use clap::Parser;
+/// use workhelix_cli_common::completions::generate_completions_named;
+///
+/// #[derive(Parser)]
+/// struct Cli {}
+///
+/// generate_completions_named::<Cli>(clap_complete::Shell::Bash, "cargo-mytool");
+/// ```
+pub fn generate_completions_named<T: CommandFactory>(shell: Shell, bin_name: &str) {
+    let _ = write_completions::<T>(shell, bin_name, &mut io::stdout().lock());
+}
+
+/// Write installation instructions followed by the completion script.
+fn write_completions<T: CommandFactory>(
+    shell: Shell,
+    bin_name: &str,
+    out: &mut impl Write,
+) -> io::Result<()> {
     let mut cmd = T::command();
-    let bin_name = cmd.get_name().to_string();
 
     // Print instructions
-    println!("# Shell completion for {bin_name}");
-    println!("#");
-    println!("# To enable completions, add this to your shell config:");
-    println!("#");
+    writeln!(out, "# Shell completion for {bin_name}")?;
+    writeln!(out, "#")?;
+    writeln!(
+        out,
+        "# To enable completions, add this to your shell config:"
+    )?;
+    writeln!(out, "#")?;
 
     match shell {
         Shell::Bash => {
-            println!("# For bash (~/.bashrc):");
-            println!("#   source <({bin_name} completions bash)");
+            writeln!(out, "# For bash (~/.bashrc):")?;
+            writeln!(out, "#   source <({bin_name} completions bash)")?;
         }
         Shell::Zsh => {
-            println!("# For zsh (~/.zshrc):");
-            println!("#   {bin_name} completions zsh > ~/.zsh/completions/_{bin_name}");
-            println!("#   # Ensure fpath includes ~/.zsh/completions");
+            writeln!(out, "# For zsh (~/.zshrc):")?;
+            writeln!(
+                out,
+                "#   {bin_name} completions zsh > ~/.zsh/completions/_{bin_name}"
+            )?;
+            writeln!(out, "#   # Ensure fpath includes ~/.zsh/completions")?;
         }
         Shell::Fish => {
-            println!("# For fish (~/.config/fish/config.fish):");
-            println!("#   {bin_name} completions fish | source");
+            writeln!(out, "# For fish (~/.config/fish/config.fish):")?;
+            writeln!(out, "#   {bin_name} completions fish | source")?;
+        }
+        Shell::Elvish => {
+            writeln!(out, "# For elvish (~/.config/elvish/rc.elv):")?;
+            writeln!(out, "#   eval ({bin_name} completions elvish | slurp)")?;
+        }
+        Shell::PowerShell => {
+            writeln!(
+                out,
+                "# For PowerShell ($PROFILE, e.g. ~\\Documents\\PowerShell\\Microsoft.PowerShell_profile.ps1):"
+            )?;
+            writeln!(
+                out,
+                "#   Add-Content $PROFILE '{bin_name} completions powershell | Out-String | Invoke-Expression'"
+            )?;
         }
         _ => {
-            println!("# For {shell}:");
-            println!("#   {bin_name} completions {shell} > /path/to/completions/_{bin_name}");
+            writeln!(out, "# For {shell}:")?;
+            writeln!(
+                out,
+                "#   {bin_name} completions {shell} > /path/to/completions/_{bin_name}"
+            )?;
         }
     }
 
-    println!();
+    writeln!(out)?;
 
     // Generate completions
-    clap_complete::generate(shell, &mut cmd, bin_name, &mut io::stdout());
+    clap_complete::generate(shell, &mut cmd, bin_name, out);
+    Ok(())
+}
+
+/// Generate a shell completion script and return it as a `String`.
+///
+/// Unlike [`generate_completions`], this returns only the raw completion script
+/// without installation instructions, which makes it suitable for writing to a
+/// file (e.g. from a `build.rs`).
+///
+/// # Examples
+/// ```
+/// use clap::Parser;
+/// use workhelix_cli_common::completions::completions_to_string;
+///
+/// #[derive(Parser)]
+/// #[command(name = "mytool")]
+/// struct Cli {}
+///
+/// let script = completions_to_string::<Cli>(clap_complete::Shell::Bash);
+/// assert!(script.contains("mytool"));
+/// ```
+#[must_use]
+pub fn completions_to_string<T: CommandFactory>(shell: Shell) -> String {
+    let mut cmd = T::command();
+    let bin_name = cmd.get_name().to_string();
+    let mut buf = Vec::new();
+    clap_complete::generate(shell, &mut cmd, bin_name, &mut buf);
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+/// The path [`install_completions`] would write to, without writing anything.
+///
+/// Useful for previewing the target of a `--dry-run` install. Filenames follow
+/// each shell's convention: `_binname` for zsh, `binname.fish` for fish,
+/// `binname.bash` for bash, `binname.elv` for elvish and `_binname.ps1` for
+/// `PowerShell`.
+///
+/// # Examples
+/// ```
+/// use clap::Parser;
+/// use std::path::Path;
+/// use workhelix_cli_common::completions::completions_install_path;
+///
+/// #[derive(Parser)]
+/// #[command(name = "mytool")]
+/// struct Cli {}
+///
+/// let path = completions_install_path::<Cli>(clap_complete::Shell::Zsh, Path::new("/completions"));
+/// assert_eq!(path, Path::new("/completions/_mytool"));
+/// ```
+#[must_use]
+pub fn completions_install_path<T: CommandFactory>(shell: Shell, dir: &Path) -> PathBuf {
+    let bin_name = T::command().get_name().to_string();
+    dir.join(shell.file_name(&bin_name))
+}
+
+/// Write a shell completion script into a directory using the shell's
+/// conventional filename.
+///
+/// The file is written to [`completions_install_path`]. The directory is
+/// created if it does not exist.
+///
+/// # Errors
+/// Returns an error if the directory cannot be created or the file cannot be written.
+///
+/// # Examples
+/// ```no_run
+/// use clap::Parser;
+/// use std::path::Path;
+/// use workhelix_cli_common::completions::install_completions;
+///
+/// #[derive(Parser)]
+/// struct Cli {}
+///
+/// let path = install_completions::<Cli>(
+///     clap_complete::Shell::Zsh,
+///     Path::new("/home/user/.zsh/completions"),
+/// )?;
+/// println!("Installed completions to {}", path.display());
+/// # Ok::<(), workhelix_cli_common::CommonError>(())
+/// ```
+pub fn install_completions<T: CommandFactory>(
+    shell: Shell,
+    dir: &Path,
+) -> Result<PathBuf, CommonError> {
+    std::fs::create_dir_all(dir)?;
+    let path = completions_install_path::<T>(shell, dir);
+    std::fs::write(&path, completions_to_string::<T>(shell))?;
+    Ok(path)
+}
+
+/// Detect the user's shell from the environment.
+///
+/// Parses the basename of `$SHELL` (e.g. `/bin/zsh` → zsh). On Windows, where
+/// `SHELL` is usually unset, `PowerShell` is detected via `PSModulePath`.
+/// Returns `None` when the shell cannot be recognized so the caller can prompt.
+///
+/// # Examples
+/// ```no_run
+/// use workhelix_cli_common::completions::detect_shell;
+///
+/// match detect_shell() {
+///     Some(shell) => println!("Detected {shell}"),
+///     None => eprintln!("Could not detect your shell; please specify one"),
+/// }
+/// ```
+#[must_use]
+pub fn detect_shell() -> Option<Shell> {
+    if let Some(shell) = std::env::var_os("SHELL") {
+        return shell_from_path(Path::new(&shell));
+    }
+    if cfg!(windows) && std::env::var_os("PSModulePath").is_some() {
+        return Some(Shell::PowerShell);
+    }
+    None
+}
+
+/// Handle a tool's `completions` subcommand in one call.
+///
+/// Uses `shell`, or the shell from [`detect_shell`] when `None`. Without
+/// `install`, the completion script and installation instructions are printed
+/// to stdout as by [`generate_completions`]. With `install`, the script is
+/// written by [`install_completions`] into `dir`, or into the shell's default
+/// user completions directory when `dir` is `None` (bash:
+/// `~/.local/share/bash-completion/completions`, zsh: `~/.zsh/completions`,
+/// fish: `~/.config/fish/completions`). Errors are printed to stderr.
+///
+/// Returns exit code: [`ExitCode::Success`] (0) on success, [`ExitCode::Failure`] (1)
+/// if no shell was given or detected, or if installation failed.
+///
+/// # Examples
+/// ```no_run
+/// use clap::Parser;
+/// use workhelix_cli_common::completions::handle_completions_command;
+///
+/// #[derive(Parser)]
+/// struct Cli {}
+///
+/// std::process::exit(handle_completions_command::<Cli>(None, false, None));
+/// ```
+#[must_use]
+pub fn handle_completions_command<T: CommandFactory>(
+    shell: Option<Shell>,
+    install: bool,
+    dir: Option<&Path>,
+) -> i32 {
+    completions_command::<T>(shell, install, dir, &mut io::stdout().lock())
+}
+
+fn completions_command<T: CommandFactory>(
+    shell: Option<Shell>,
+    install: bool,
+    dir: Option<&Path>,
+    out: &mut impl Write,
+) -> i32 {
+    let Some(shell) = shell.or_else(detect_shell) else {
+        output::eprint_error(
+            "Could not detect your shell; specify one of: bash, zsh, fish, elvish, powershell",
+        );
+        return ExitCode::Failure.as_i32();
+    };
+    let bin_name = T::command().get_name().to_string();
+
+    if !install {
+        return match write_completions::<T>(shell, &bin_name, out) {
+            Ok(()) => ExitCode::Success.as_i32(),
+            Err(e) => {
+                output::eprint_error(&format!("Failed to write completions: {e}"));
+                ExitCode::Failure.as_i32()
+            }
+        };
+    }
+
+    let Some(dir) = dir
+        .map(Path::to_path_buf)
+        .or_else(|| default_completions_dir(shell))
+    else {
+        output::eprint_error(&format!(
+            "No default completions directory for {shell}; specify one"
+        ));
+        return ExitCode::Failure.as_i32();
+    };
+    match install_completions::<T>(shell, &dir) {
+        Ok(path) => {
+            let _ = writeln!(
+                out,
+                "{}",
+                output::success(&format!(
+                    "Installed {shell} completions to {}",
+                    path.display()
+                ))
+            );
+            ExitCode::Success.as_i32()
+        }
+        Err(e) => {
+            output::eprint_error(&format!(
+                "Failed to install completions to {}: {e}",
+                dir.display()
+            ));
+            ExitCode::Failure.as_i32()
+        }
+    }
+}
+
+/// The per-user directory each shell loads completions from, if it has one.
+fn default_completions_dir(shell: Shell) -> Option<PathBuf> {
+    let relative = match shell {
+        Shell::Bash => ".local/share/bash-completion/completions",
+        Shell::Zsh => ".zsh/completions",
+        Shell::Fish => ".config/fish/completions",
+        _ => return None,
+    };
+    home::home_dir().map(|home| home.join(relative))
+}
+
+/// Map a shell executable path to a `Shell` by its basename.
+fn shell_from_path(path: &Path) -> Option<Shell> {
+    match path.file_stem()?.to_str()? {
+        "bash" => Some(Shell::Bash),
+        "zsh" => Some(Shell::Zsh),
+        "fish" => Some(Shell::Fish),
+        "elvish" => Some(Shell::Elvish),
+        "pwsh" | "powershell" => Some(Shell::PowerShell),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -110,6 +391,155 @@ mod tests {
         generate_completions::<TestCli>(Shell::PowerShell);
     }
 
+    #[test]
+    fn test_completions_to_string_bash() {
+        let script = completions_to_string::<TestCli>(Shell::Bash);
+        assert!(script.contains("test-cli"));
+        assert!(script.contains("complete"));
+        assert!(!script.starts_with("# Shell completion"));
+    }
+
+    #[test]
+    fn test_install_completions() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("completions");
+
+        let path = install_completions::<TestCli>(Shell::Zsh, &target).unwrap();
+        assert_eq!(path, target.join("_test-cli"));
+        assert!(std::fs::metadata(&path).unwrap().len() > 0);
+
+        let path = install_completions::<TestCli>(Shell::Fish, &target).unwrap();
+        assert_eq!(path, target.join("test-cli.fish"));
+
+        let path = install_completions::<TestCli>(Shell::Bash, &target).unwrap();
+        assert_eq!(path, target.join("test-cli.bash"));
+    }
+
+    #[test]
+    fn test_completions_install_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("completions");
+
+        let path = completions_install_path::<TestCli>(Shell::Zsh, &target);
+        assert!(path.ends_with("_test-cli"));
+        assert!(!target.exists());
+
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            assert_eq!(
+                install_completions::<TestCli>(shell, &target).unwrap(),
+                completions_install_path::<TestCli>(shell, &target)
+            );
+        }
+    }
+
+    #[test]
+    fn test_shell_from_path() {
+        assert_eq!(shell_from_path(Path::new("/bin/zsh")), Some(Shell::Zsh));
+        assert_eq!(
+            shell_from_path(Path::new("/usr/bin/fish")),
+            Some(Shell::Fish)
+        );
+        assert_eq!(
+            shell_from_path(Path::new("/usr/local/bin/bash")),
+            Some(Shell::Bash)
+        );
+        assert_eq!(
+            shell_from_path(Path::new("/usr/bin/pwsh")),
+            Some(Shell::PowerShell)
+        );
+        assert_eq!(shell_from_path(Path::new("/bin/tcsh")), None);
+        assert_eq!(shell_from_path(Path::new("")), None);
+    }
+
+    #[test]
+    fn test_detect_shell() {
+        crate::test_env::with_var("SHELL", Some("/bin/zsh"), || {
+            assert_eq!(detect_shell(), Some(Shell::Zsh));
+        });
+        crate::test_env::with_var("SHELL", Some("/bin/unknown-shell"), || {
+            assert_eq!(detect_shell(), None);
+        });
+    }
+
+    #[test]
+    fn test_completions_command_no_shell_detected() {
+        crate::test_env::with_vars(&[("SHELL", None), ("PSModulePath", None)], || {
+            let mut buf = Vec::new();
+            let code = completions_command::<TestCli>(None, false, None, &mut buf);
+            assert_eq!(code, 1);
+            assert!(buf.is_empty());
+        });
+    }
+
+    #[test]
+    fn test_completions_command_prints_script() {
+        let mut buf = Vec::new();
+        let code = completions_command::<TestCli>(Some(Shell::Bash), false, None, &mut buf);
+        assert_eq!(code, 0);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.starts_with("# Shell completion for test-cli"));
+        assert!(output.contains(&completions_to_string::<TestCli>(Shell::Bash)));
+    }
+
+    #[test]
+    fn test_completions_command_install() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut buf = Vec::new();
+        let code =
+            completions_command::<TestCli>(Some(Shell::Fish), true, Some(dir.path()), &mut buf);
+        assert_eq!(code, 0);
+        assert!(dir.path().join("test-cli.fish").is_file());
+        assert!(
+            String::from_utf8(buf)
+                .unwrap()
+                .contains("Installed fish completions")
+        );
+    }
+
+    #[test]
+    fn test_default_completions_dir() {
+        crate::test_env::with_var("HOME", Some("/home/tester"), || {
+            assert_eq!(
+                default_completions_dir(Shell::Zsh),
+                Some(PathBuf::from("/home/tester/.zsh/completions"))
+            );
+            assert_eq!(default_completions_dir(Shell::Elvish), None);
+        });
+    }
+
+    #[test]
+    fn test_powershell_instructions() {
+        let mut buf = Vec::new();
+        write_completions::<TestCli>(Shell::PowerShell, "test-cli", &mut buf).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("$PROFILE"));
+        assert!(output.contains(
+            "Add-Content $PROFILE 'test-cli completions powershell | Out-String | Invoke-Expression'"
+        ));
+        assert!(!output.contains("_test-cli"));
+    }
+
+    #[test]
+    fn test_elvish_instructions() {
+        let mut buf = Vec::new();
+        write_completions::<TestCli>(Shell::Elvish, "test-cli", &mut buf).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("~/.config/elvish/rc.elv"));
+        assert!(output.contains("eval (test-cli completions elvish | slurp)"));
+    }
+
+    #[test]
+    fn test_generate_completions_named() {
+        let mut buf = Vec::new();
+        write_completions::<TestCli>(Shell::Bash, "cargo-test-cli", &mut buf).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("# Shell completion for cargo-test-cli"));
+        assert!(output.contains("source <(cargo-test-cli completions bash)"));
+        assert!(output.contains("-o default cargo-test-cli"));
+
+        generate_completions_named::<TestCli>(Shell::Zsh, "cargo-test-cli");
+    }
+
     #[test]
     fn test_all_shells_generate_without_panic() {
         let shells = vec![
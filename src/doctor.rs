@@ -3,7 +3,21 @@
 //! This module provides a framework for running health checks on CLI tools
 //! with tool-specific diagnostics.
 
-use crate::types::{DoctorCheck, RepoInfo};
+use crate::output::{self, OutputFormat};
+#[cfg(feature = "tokio")]
+use crate::types::AsyncCheck;
+use crate::types::{
+    DoctorCheck, DoctorNode, DoctorSection, ExitCode, PendingCheck, RepoInfo, Severity,
+};
+use std::any::Any;
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Default time a pending check may run before it is reported as timed out.
+pub const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Trait for tools that support doctor health checks.
 ///
@@ -21,82 +35,2051 @@ pub trait DoctorChecks {
     fn tool_checks(&self) -> Vec<DoctorCheck> {
         Vec::new()
     }
+
+    /// Run tool-specific health checks, or report that they could not be run.
+    ///
+    /// Override this instead of `tool_checks` when computing the checks can
+    /// itself fail (e.g., the config directory is unreadable). An `Err` aborts
+    /// the doctor run and is reported as a single top-level failure, distinct
+    /// from a failing check. Default implementation returns `Ok(self.tool_checks())`.
+    ///
+    /// # Errors
+    /// Returns a description of why the checks could not be computed.
+    fn try_tool_checks(&self) -> Result<Vec<DoctorCheck>, String> {
+        Ok(self.tool_checks())
+    }
+
+    /// Run tool-specific health checks grouped into labeled sections.
+    ///
+    /// Sections are rendered after the flat `tool_checks` list, which appears
+    /// under "Configuration". Default implementation returns empty vector.
+    fn tool_sections(&self) -> Vec<DoctorSection> {
+        Vec::new()
+    }
+
+    /// Run tool-specific health checks nested more than one level deep.
+    ///
+    /// Rendered after `tool_sections`, as a tree with `├──`/`└──` connectors
+    /// on a terminal and plain indentation otherwise. Default implementation
+    /// returns empty vector.
+    fn tool_tree(&self) -> Vec<DoctorNode> {
+        Vec::new()
+    }
+
+    /// Provide checks to run concurrently, each bounded by `check_timeout`.
+    ///
+    /// Use this for slow or network-bound checks that might hang. Results are
+    /// reported under "Configuration" after `tool_checks`, in the order returned
+    /// here regardless of which finishes first. A check that exceeds the timeout
+    /// is reported as failed. Default implementation returns empty vector.
+    fn tool_checks_parallel(&self) -> Vec<PendingCheck> {
+        Vec::new()
+    }
+
+    /// Maximum time each parallel check may run. Defaults to 10 seconds.
+    fn check_timeout(&self) -> Duration {
+        DEFAULT_CHECK_TIMEOUT
+    }
+
+    /// Whether to print a system-info preamble (tool version, target triple and
+    /// OS version) before the checks, to make bug reports easier. Defaults to `true`.
+    fn include_system_info(&self) -> bool {
+        true
+    }
 }
 
-/// Run doctor command to check health and configuration.
+/// Trait for tools whose doctor checks are async, e.g. network-bound diagnostics.
+///
+/// The async counterpart of [`DoctorChecks`], run with [`run_doctor_async`].
+/// `tool_checks` and every entry of `concurrent_checks` are polled concurrently.
+///
+/// # Examples
+/// ```no_run
+/// use workhelix_cli_common::doctor::{AsyncDoctorChecks, run_doctor_async};
+/// use workhelix_cli_common::{AsyncCheck, DoctorCheck, RepoInfo};
+///
+/// struct MyTool;
+///
+/// impl AsyncDoctorChecks for MyTool {
+///     fn repo_info() -> RepoInfo {
+///         RepoInfo::new("myorg", "mytool", "v")
+///     }
+///
+///     fn current_version() -> &'static str {
+///         env!("CARGO_PKG_VERSION")
+///     }
+///
+///     fn concurrent_checks(&self) -> Vec<AsyncCheck<'_>> {
+///         vec![AsyncCheck::new("API reachable", async {
+///             DoctorCheck::pass("API reachable")
+///         })]
+///     }
+/// }
+///
+/// # async fn example() {
+/// let exit_code = run_doctor_async(&MyTool).await;
+/// # }
+/// ```
+#[cfg(feature = "tokio")]
+pub trait AsyncDoctorChecks {
+    /// Get the repository information for this tool.
+    fn repo_info() -> RepoInfo;
+
+    /// Get the current version of this tool.
+    fn current_version() -> &'static str;
+
+    /// Run tool-specific health checks.
+    ///
+    /// Implement with `async fn`. Default implementation returns empty vector.
+    fn tool_checks(&self) -> impl Future<Output = Vec<DoctorCheck>> + Send {
+        async { Vec::new() }
+    }
+
+    /// Provide individual checks to run concurrently, each bounded by `check_timeout`.
+    ///
+    /// Results are reported after `tool_checks`, in the order returned here.
+    /// Default implementation returns empty vector.
+    fn concurrent_checks(&self) -> Vec<AsyncCheck<'_>> {
+        Vec::new()
+    }
+
+    /// Maximum time each concurrent check may run. Defaults to 10 seconds.
+    fn check_timeout(&self) -> Duration {
+        DEFAULT_CHECK_TIMEOUT
+    }
+}
+
+/// Exit code returned for warning-only runs when `fail_on_warning` is set.
+pub const DEFAULT_WARNING_EXIT_CODE: i32 = 2;
+
+/// Options controlling how `run_doctor_with_options` presents and scores results.
+///
+/// Exit-code contract:
+/// - 0: every check passed, or only warnings were found and `fail_on_warning` is off
+/// - 1: at least one check failed (always wins over warnings)
+/// - `warning_exit_code` (default 2): no failures, but warnings were found and
+///   `fail_on_warning` is on
+#[derive(Debug, Clone)]
+pub struct DoctorOptions {
+    /// Only print failures and warnings plus the summary; when everything
+    /// passes, print a single success line.
+    pub quiet: bool,
+    /// Treat warnings as a failed run (e.g., in CI).
+    pub fail_on_warning: bool,
+    /// Exit code for warning-only runs when `fail_on_warning` is set.
+    pub warning_exit_code: i32,
+    /// Print how long each timed check took, e.g. `✅ Network (123ms)`.
+    pub show_timings: bool,
+}
+
+impl Default for DoctorOptions {
+    fn default() -> Self {
+        Self {
+            quiet: false,
+            fail_on_warning: false,
+            warning_exit_code: DEFAULT_WARNING_EXIT_CODE,
+            show_timings: false,
+        }
+    }
+}
+
+/// Platform details shown in the doctor preamble.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SystemInfo {
+    /// Target triple of the running binary (e.g., "x86_64-unknown-linux-gnu")
+    pub target: String,
+    /// Operating system version, if it could be determined
+    pub os_version: Option<String>,
+}
+
+impl SystemInfo {
+    /// Detect the platform the binary is running on.
+    #[must_use]
+    pub fn current() -> Self {
+        Self {
+            target: crate::platform::target_triple(),
+            os_version: crate::platform::os_version(),
+        }
+    }
+}
+
+/// Results of a doctor run, independent of how they are presented.
 ///
-/// Returns exit code: 0 if healthy, 1 if issues found.
+/// Build one with `collect_doctor_report` to embed doctor results in another UI
+/// (e.g., a TUI) or to test checks without capturing stdout.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DoctorReport {
+    /// Name of the tool that was checked
+    pub tool_name: String,
+    /// Version of the tool that was checked
+    pub version: String,
+    /// Ungrouped checks from `tool_checks` and `tool_checks_parallel`
+    pub checks: Vec<DoctorCheck>,
+    /// Labeled sections from `tool_sections`
+    pub sections: Vec<DoctorSection>,
+    /// Nested checks from `tool_tree`
+    pub tree: Vec<DoctorNode>,
+    /// Number of passing checks across all sections
+    pub passed: usize,
+    /// Number of failing checks across all sections
+    pub failed: usize,
+    /// Number of warnings across all sections
+    pub warnings: usize,
+    /// Why the checks could not be run, if `try_tool_checks` failed
+    pub error: Option<String>,
+    /// Platform details for the preamble, if the tool includes them
+    pub system_info: Option<SystemInfo>,
+}
+
+impl DoctorReport {
+    /// Create a report, tallying results across `checks` and `sections`.
+    #[must_use]
+    pub fn new(
+        tool_name: impl Into<String>,
+        version: impl Into<String>,
+        checks: Vec<DoctorCheck>,
+        sections: Vec<DoctorSection>,
+    ) -> Self {
+        let mut report = Self {
+            tool_name: tool_name.into(),
+            version: version.into(),
+            checks,
+            sections,
+            tree: Vec::new(),
+            passed: 0,
+            failed: 0,
+            warnings: 0,
+            error: None,
+            system_info: None,
+        };
+        report.tally();
+        report
+    }
+
+    /// Add nested checks to the report, updating the tallies.
+    #[must_use]
+    pub fn with_tree(mut self, tree: Vec<DoctorNode>) -> Self {
+        self.tree = tree;
+        self.tally();
+        self
+    }
+
+    /// Recount passes, failures and warnings across every check.
+    fn tally(&mut self) {
+        let (mut passed, mut failed, mut warnings) = (0, 0, 0);
+        for check in self.all_checks() {
+            match check.severity {
+                Severity::Pass => passed += 1,
+                Severity::Warn => warnings += 1,
+                Severity::Fail => failed += 1,
+            }
+        }
+        self.passed = passed;
+        self.failed = failed;
+        self.warnings = warnings;
+    }
+
+    /// Create a report for a run whose checks could not be computed.
+    ///
+    /// The report has no checks but counts as one failure.
+    #[must_use]
+    pub fn setup_failed(
+        tool_name: impl Into<String>,
+        version: impl Into<String>,
+        error: impl Into<String>,
+    ) -> Self {
+        Self {
+            failed: 1,
+            error: Some(error.into()),
+            ..Self::new(tool_name, version, Vec::new(), Vec::new())
+        }
+    }
+
+    /// Iterate over every check: ungrouped checks first, then each section's,
+    /// then the tree's, depth-first.
+    pub fn all_checks(&self) -> impl Iterator<Item = &DoctorCheck> {
+        self.checks
+            .iter()
+            .chain(self.sections.iter().flat_map(|section| &section.checks))
+            .chain(self.tree.iter().flat_map(DoctorNode::checks))
+    }
+
+    /// Exit code for this report: [`ExitCode::Success`] (0) if healthy or only
+    /// warnings were found, [`ExitCode::Failure`] (1) if any check failed.
+    #[must_use]
+    pub const fn exit_code(&self) -> i32 {
+        if self.failed > 0 {
+            ExitCode::Failure.as_i32()
+        } else {
+            ExitCode::Success.as_i32()
+        }
+    }
+
+    /// Exit code for this report under `options`, following the contract
+    /// documented on [`DoctorOptions`].
+    #[must_use]
+    pub const fn exit_code_with(&self, options: &DoctorOptions) -> i32 {
+        if self.failed > 0 {
+            ExitCode::Failure.as_i32()
+        } else if self.warnings > 0 && options.fail_on_warning {
+            options.warning_exit_code
+        } else {
+            ExitCode::Success.as_i32()
+        }
+    }
+}
+
+/// Run all of a tool's checks and collect the results without printing anything.
 ///
 /// # Type Parameters
 /// * `T` - A type that implements `DoctorChecks`
-pub fn run_doctor<T: DoctorChecks>(tool: &T) -> i32 {
-    let tool_name = T::repo_info().name;
-    println!("🏥 {tool_name} health check");
-    println!("{}", "=".repeat(tool_name.len() + 14));
-    println!();
-
-    let mut has_errors = false;
-    let has_warnings = false;
-
-    // Run tool-specific checks
-    let tool_checks = tool.tool_checks();
-    if !tool_checks.is_empty() {
-        println!("Configuration:");
-        for check in tool_checks {
-            if check.passed {
-                println!("  ✅ {}", check.name);
+///
+/// If `try_tool_checks` fails, no other checks are run and the report carries
+/// the error instead (see [`DoctorReport::setup_failed`]).
+pub fn collect_doctor_report<T: DoctorChecks>(tool: &T) -> DoctorReport {
+    collect_report(tool, None)
+}
+
+/// Collect the report, giving up on checks that have not finished by `deadline`.
+///
+/// Sections and the tree are computed before the parallel checks are waited
+/// on, so the deadline is spent on whichever checks are still running. Parallel
+/// checks still running at the deadline are reported as skipped by name; if
+/// the deadline passes before the `tool_sections` or `tool_tree` call is made,
+/// that call is skipped and a single "Remaining checks" entry is reported as
+/// skipped in its place.
+fn collect_report<T: DoctorChecks>(tool: &T, deadline: Option<Instant>) -> DoctorReport {
+    let expired = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
+    let mut report = match tool.try_tool_checks() {
+        Ok(mut checks) => {
+            let mut skipped = expired();
+            let sections = if skipped {
+                Vec::new()
             } else {
-                println!("  ❌ {}", check.name);
-                if let Some(msg) = check.message {
-                    println!("     {msg}");
-                }
-                has_errors = true;
+                tool.tool_sections()
+            };
+            skipped = skipped || expired();
+            let tree = if skipped {
+                Vec::new()
+            } else {
+                tool.tool_tree()
+            };
+            checks.extend(run_pending_checks(
+                tool.tool_checks_parallel(),
+                tool.check_timeout(),
+                deadline,
+            ));
+            if skipped {
+                checks.push(DoctorCheck::fail("Remaining checks", DEADLINE_MESSAGE));
             }
+            DoctorReport::new(T::repo_info().name, T::current_version(), checks, sections)
+                .with_tree(tree)
+        }
+        Err(e) => DoctorReport::setup_failed(T::repo_info().name, T::current_version(), e),
+    };
+    if tool.include_system_info() {
+        report.system_info = Some(SystemInfo::current());
+    }
+    report
+}
+
+/// List the names of all checks a tool performs, in report order.
+///
+/// Useful for a `doctor --list-checks` flag. Checks from `tool_checks_parallel`
+/// are listed without running them, since `PendingCheck` carries its name up
+/// front. `tool_checks` and `tool_sections` return finished results, so they
+/// are executed to learn their names; if `try_tool_checks` fails, its checks
+/// are omitted.
+///
+/// # Type Parameters
+/// * `T` - A type that implements `DoctorChecks`
+#[must_use]
+pub fn list_doctor_checks<T: DoctorChecks>(tool: &T) -> Vec<String> {
+    let mut names: Vec<String> = tool
+        .try_tool_checks()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|check| check.name)
+        .collect();
+    names.extend(
+        tool.tool_checks_parallel()
+            .into_iter()
+            .map(|check| check.name),
+    );
+    names.extend(
+        tool.tool_sections()
+            .into_iter()
+            .flat_map(|section| section.checks)
+            .map(|check| check.name),
+    );
+    for node in tool.tool_tree() {
+        names.extend(node.checks().into_iter().map(|check| check.name.clone()));
+    }
+    names
+}
+
+/// Stream a tool's check results one at a time, in report order.
+///
+/// Useful for a live-updating UI. Each group of checks is computed only when
+/// the iterator reaches it: `tool_checks`, then `tool_checks_parallel` (run
+/// concurrently as in [`collect_doctor_report`]), then `tool_sections`, then
+/// `tool_tree`, so earlier results can be shown while later groups are still
+/// to run. If `try_tool_checks` fails, a single "Doctor could not run" failure
+/// is yielded and nothing else is run.
+///
+/// # Type Parameters
+/// * `T` - A type that implements `DoctorChecks`
+pub fn stream_doctor_checks<T: DoctorChecks>(tool: &T) -> impl Iterator<Item = DoctorCheck> + '_ {
+    std::iter::once_with(move || tool.try_tool_checks()).flat_map(move |checks| {
+        let (checks, run_rest) = match checks {
+            Ok(checks) => (checks, true),
+            Err(e) => (vec![DoctorCheck::fail("Doctor could not run", e)], false),
+        };
+        let parallel = std::iter::once_with(move || {
+            run_pending_checks(tool.tool_checks_parallel(), tool.check_timeout(), None)
+        });
+        let sections = std::iter::once_with(move || {
+            tool.tool_sections()
+                .into_iter()
+                .flat_map(|section| section.checks)
+                .collect()
+        });
+        let tree = std::iter::once_with(move || {
+            tool.tool_tree()
+                .iter()
+                .flat_map(DoctorNode::checks)
+                .cloned()
+                .collect()
+        });
+        let rest = run_rest.then(|| parallel.chain(sections).chain(tree).flatten());
+        checks.into_iter().chain(rest.into_iter().flatten())
+    })
+}
+
+/// Run all of a tool's async checks concurrently and collect the results.
+///
+/// Must be polled within a tokio runtime, which provides the timers used for
+/// `check_timeout`.
+///
+/// # Type Parameters
+/// * `T` - A type that implements `AsyncDoctorChecks`
+#[cfg(feature = "tokio")]
+pub async fn collect_doctor_report_async<T: AsyncDoctorChecks + Sync>(tool: &T) -> DoctorReport {
+    let timeout = tool.check_timeout();
+    let concurrent = futures_util::future::join_all(tool.concurrent_checks().into_iter().map(
+        |check| async move {
+            tokio::time::timeout(timeout, check.run)
+                .await
+                .unwrap_or_else(|_| {
+                    DoctorCheck::fail(check.name, format!("Check timed out after {timeout:?}"))
+                })
+        },
+    ));
+    let (mut checks, concurrent) = futures_util::future::join(tool.tool_checks(), concurrent).await;
+    checks.extend(concurrent);
+    DoctorReport::new(
+        T::repo_info().name,
+        T::current_version(),
+        checks,
+        Vec::new(),
+    )
+}
+
+/// Run async doctor checks and print the report to stdout.
+///
+/// The async counterpart of `run_doctor`; must be awaited within a tokio runtime.
+///
+/// Returns exit code: [`ExitCode::Success`] (0) if healthy or only warnings were
+/// found, [`ExitCode::Failure`] (1) if any check failed.
+///
+/// # Type Parameters
+/// * `T` - A type that implements `AsyncDoctorChecks`
+#[cfg(feature = "tokio")]
+pub async fn run_doctor_async<T: AsyncDoctorChecks + Sync>(tool: &T) -> i32 {
+    let report = collect_doctor_report_async(tool).await;
+    let _ = write_report(&report, &DoctorOptions::default(), &mut io::stdout().lock());
+    report.exit_code()
+}
+
+/// Run pending checks concurrently, one thread per check.
+///
+/// Every check starts at the same time, so each gets `timeout` to finish.
+/// Results keep the input order. Checks still running when the timeout expires
+/// are reported as failed and their threads are left to finish in the background.
+/// If `deadline` comes first, checks still running then are reported as skipped.
+fn run_pending_checks(
+    checks: Vec<PendingCheck>,
+    timeout: Duration,
+    deadline: Option<Instant>,
+) -> Vec<DoctorCheck> {
+    if checks.is_empty() {
+        return Vec::new();
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let mut names = Vec::with_capacity(checks.len());
+    for (index, check) in checks.into_iter().enumerate() {
+        let name_for_panic = check.name.clone();
+        names.push(check.name);
+        let run = check.run;
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let started = Instant::now();
+            let mut check = panic::catch_unwind(AssertUnwindSafe(run)).unwrap_or_else(|payload| {
+                DoctorCheck::fail(name_for_panic, panic_message(&*payload))
+            });
+            check.duration.get_or_insert_with(|| started.elapsed());
+            // The receiver may have given up waiting; nothing to do then.
+            let _ = tx.send((index, check));
+        });
+    }
+    drop(tx);
+
+    let timeout_at = Instant::now() + timeout;
+    let past_deadline = deadline.is_some_and(|deadline| deadline < timeout_at);
+    let wait_until = deadline.map_or(timeout_at, |deadline| deadline.min(timeout_at));
+    let mut results: Vec<Option<DoctorCheck>> = vec![None; names.len()];
+    while results.iter().any(Option::is_none) {
+        let remaining = wait_until.saturating_duration_since(Instant::now());
+        match rx.recv_timeout(remaining) {
+            Ok((index, check)) => results[index] = Some(check),
+            Err(_) => break,
+        }
+    }
+
+    results
+        .into_iter()
+        .zip(names)
+        .map(|(result, name)| {
+            result.unwrap_or_else(|| {
+                if past_deadline {
+                    DoctorCheck::fail(name, DEADLINE_MESSAGE)
+                } else {
+                    DoctorCheck::fail(name, format!("Check timed out after {timeout:?}"))
+                }
+            })
+        })
+        .collect()
+}
+
+/// Describe a panic payload caught from a parallel check.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let detail = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str));
+    detail.map_or_else(
+        || "Check panicked".to_string(),
+        |detail| format!("Check panicked: {detail}"),
+    )
+}
+
+/// Run doctor command to check health and configuration.
+///
+/// Returns exit code: [`ExitCode::Success`] (0) if healthy or only warnings were
+/// found, [`ExitCode::Failure`] (1) if any check failed.
+///
+/// # Type Parameters
+/// * `T` - A type that implements `DoctorChecks`
+pub fn run_doctor<T: DoctorChecks>(tool: &T) -> i32 {
+    run_doctor_to(tool, &mut io::stdout().lock())
+}
+
+/// Run doctor command, writing the human-readable report to `out`.
+///
+/// Behaves exactly like `run_doctor` but lets the caller capture or redirect the
+/// output (e.g., into a `Vec<u8>` in tests or a log file). Write errors, such as
+/// a closed pipe, are ignored; the exit code still reflects the check results.
+///
+/// Returns exit code: [`ExitCode::Success`] (0) if healthy or only warnings were
+/// found, [`ExitCode::Failure`] (1) if any check failed.
+///
+/// # Type Parameters
+/// * `T` - A type that implements `DoctorChecks`
+/// * `W` - The output destination
+pub fn run_doctor_to<T: DoctorChecks, W: Write>(tool: &T, out: &mut W) -> i32 {
+    run_doctor_with_options_to(tool, &DoctorOptions::default(), out)
+}
+
+/// Run doctor command within an overall time budget, printing to stdout.
+///
+/// Beyond each parallel check's `check_timeout`, the whole run is bounded by
+/// `deadline`: parallel checks still running when it expires are reported as
+/// failed with "Skipped (deadline exceeded)", and sections or tree checks not
+/// yet computed are replaced by one "Remaining checks" entry reported the same
+/// way. Checks from `tool_checks`, `tool_sections` and `tool_tree` run
+/// synchronously and cannot be interrupted.
+///
+/// Returns exit code: [`ExitCode::Success`] (0) if healthy or only warnings were
+/// found, [`ExitCode::Failure`] (1) if any check failed or was skipped.
+///
+/// # Type Parameters
+/// * `T` - A type that implements `DoctorChecks`
+pub fn run_doctor_with_deadline<T: DoctorChecks>(tool: &T, deadline: Duration) -> i32 {
+    let report = collect_report(tool, Some(Instant::now() + deadline));
+    let _ = write_report(&report, &DoctorOptions::default(), &mut io::stdout().lock());
+    report.exit_code()
+}
+
+/// Message for checks that did not finish before the doctor deadline.
+const DEADLINE_MESSAGE: &str = "Skipped (deadline exceeded)";
+
+/// Run doctor command with options, printing to stdout.
+///
+/// Exit codes are the same as `run_doctor` unless `options.fail_on_warning` is
+/// set, in which case a warning-only run returns `options.warning_exit_code`.
+/// See [`DoctorOptions`] for the full contract.
+///
+/// # Type Parameters
+/// * `T` - A type that implements `DoctorChecks`
+pub fn run_doctor_with_options<T: DoctorChecks>(tool: &T, options: &DoctorOptions) -> i32 {
+    run_doctor_with_options_to(tool, options, &mut io::stdout().lock())
+}
+
+/// Run doctor command with options, writing the report to `out`.
+///
+/// Exit codes follow the contract documented on [`DoctorOptions`].
+///
+/// # Type Parameters
+/// * `T` - A type that implements `DoctorChecks`
+/// * `W` - The output destination
+pub fn run_doctor_with_options_to<T: DoctorChecks, W: Write>(
+    tool: &T,
+    options: &DoctorOptions,
+    out: &mut W,
+) -> i32 {
+    let report = collect_doctor_report(tool);
+    let _ = write_report(&report, options, out);
+    report.exit_code_with(options)
+}
+
+fn write_report<W: Write>(
+    report: &DoctorReport,
+    options: &DoctorOptions,
+    out: &mut W,
+) -> io::Result<()> {
+    if options.quiet && report.failed == 0 && report.warnings == 0 {
+        return writeln!(out, "{} Everything looks healthy!", icon("✨", "[OK]"));
+    }
+
+    if !options.quiet {
+        let tool_name = &report.tool_name;
+        if output::emoji_enabled() {
+            writeln!(out, "🏥 {tool_name} health check")?;
+        } else {
+            writeln!(out, "{tool_name} health check")?;
         }
-        println!();
+        writeln!(out, "{}", "=".repeat(tool_name.len() + 14))?;
+        writeln!(out)?;
+
+        if let Some(system) = &report.system_info {
+            let os = system.os_version.as_deref().unwrap_or("unknown");
+            writeln!(
+                out,
+                "{}",
+                output::kv_list(&[
+                    ("Version", &report.version),
+                    ("Target", &system.target),
+                    ("OS", os),
+                ])
+            )?;
+            writeln!(out)?;
+        }
+    }
+
+    if let Some(error) = &report.error {
+        return writeln!(
+            out,
+            "{} Doctor could not run: {error}",
+            icon("❌", "[FAIL]")
+        );
+    }
+
+    write_section(out, "Configuration", &report.checks, options)?;
+    for section in &report.sections {
+        write_section(out, &section.title, &section.checks, options)?;
+    }
+    let glyphs = if output::is_tty() && output::unicode_supported() {
+        &BOX_TREE
+    } else {
+        &PLAIN_TREE
+    };
+    for node in &report.tree {
+        write_tree(out, node, glyphs, options)?;
     }
 
     // Summary
-    if has_errors {
-        println!("❌ Issues found - see above for details");
-        1
-    } else if has_warnings {
-        println!("⚠️  Warnings found");
-        0 // Warnings don't cause failure
+    writeln!(
+        out,
+        "{}",
+        output::check_counts(report.passed, report.failed, report.warnings)
+    )?;
+    let (fail, warn) = (icon("❌", "[FAIL]"), icon("⚠️ ", "[WARN]"));
+    let issues = output::pluralize(report.failed, "issue", "issues");
+    let warnings = output::pluralize(report.warnings, "warning", "warnings");
+    if report.failed > 0 {
+        writeln!(out, "{fail} {issues} found - see above for details")
+    } else if report.warnings > 0 && options.fail_on_warning {
+        writeln!(out, "{fail} {warnings} found - treated as failures")
+    } else if report.warnings > 0 {
+        writeln!(out, "{warn} {warnings} found") // Warnings don't cause failure by default
     } else {
-        println!("✨ Everything looks healthy!");
-        0
+        writeln!(out, "{} Everything looks healthy!", icon("✨", "[OK]"))
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn write_section<W: Write>(
+    out: &mut W,
+    title: &str,
+    checks: &[DoctorCheck],
+    options: &DoctorOptions,
+) -> io::Result<()> {
+    let checks: Vec<&DoctorCheck> = checks
+        .iter()
+        .filter(|check| !options.quiet || check.severity != Severity::Pass)
+        .collect();
+    if checks.is_empty() {
+        return Ok(());
+    }
+    let body: Vec<String> = checks
+        .into_iter()
+        .flat_map(|check| check_lines(check, options))
+        .collect();
+    writeln!(
+        out,
+        "{}",
+        output::section(&format!("{title}:"), &body.join("\n"))
+    )?;
+    writeln!(out)
+}
 
-    struct TestTool;
+/// The marker line for `check`, followed by its message and remediation.
+fn check_lines(check: &DoctorCheck, options: &DoctorOptions) -> Vec<String> {
+    let timing = match check.duration {
+        Some(duration) if options.show_timings => {
+            format!(" ({})", output::format_duration(duration))
+        }
+        _ => String::new(),
+    };
+    let marker = match check.severity {
+        Severity::Pass => icon("✅", "[PASS]"),
+        Severity::Warn => icon("⚠️ ", "[WARN]"),
+        Severity::Fail => icon("❌", "[FAIL]"),
+    };
+    let mut lines = vec![format!("{marker} {}{timing}", check.name)];
+    if check.severity != Severity::Pass {
+        if let Some(msg) = &check.message {
+            lines.push(output::indent(msg, 3));
+        }
+    }
+    if check.severity == Severity::Fail {
+        if let Some(remediation) = &check.remediation {
+            lines.push(format!("   {} try: {remediation}", icon("→", "->")));
+        }
+    }
+    lines
+}
 
-    impl DoctorChecks for TestTool {
-        fn repo_info() -> RepoInfo {
-            RepoInfo::new("workhelix", "test-tool")
+/// Prefixes drawn before tree nodes: a branch, the last branch, and the
+/// continuations below each.
+struct TreeGlyphs {
+    branch: &'static str,
+    last: &'static str,
+    pipe: &'static str,
+    space: &'static str,
+}
+
+/// Box-drawing connectors for terminals.
+const BOX_TREE: TreeGlyphs = TreeGlyphs {
+    branch: "├── ",
+    last: "└── ",
+    pipe: "│   ",
+    space: "    ",
+};
+
+/// Plain indentation for pipes and files.
+const PLAIN_TREE: TreeGlyphs = TreeGlyphs {
+    branch: "  ",
+    last: "  ",
+    pipe: "  ",
+    space: "  ",
+};
+
+/// Write a top-level tree node: a section as a titled block with its children
+/// drawn as a tree below, or a lone check.
+fn write_tree<W: Write>(
+    out: &mut W,
+    node: &DoctorNode,
+    glyphs: &TreeGlyphs,
+    options: &DoctorOptions,
+) -> io::Result<()> {
+    if !node_visible(node, options) {
+        return Ok(());
+    }
+    match node {
+        DoctorNode::Check(check) => writeln!(out, "{}", check_lines(check, options).join("\n")),
+        DoctorNode::Section { title, children } => {
+            let mut body = Vec::new();
+            tree_lines(&mut body, children, "", glyphs, options);
+            writeln!(
+                out,
+                "{}",
+                output::section(&format!("{title}:"), &body.join("\n"))
+            )?;
+            writeln!(out)
         }
+    }
+}
 
-        fn current_version() -> &'static str {
-            "1.0.0"
+/// Append the lines for `nodes` drawn below `prefix`; the last visible node
+/// gets the closing connector.
+fn tree_lines(
+    lines: &mut Vec<String>,
+    nodes: &[DoctorNode],
+    prefix: &str,
+    glyphs: &TreeGlyphs,
+    options: &DoctorOptions,
+) {
+    let visible: Vec<&DoctorNode> = nodes
+        .iter()
+        .filter(|node| node_visible(node, options))
+        .collect();
+    let count = visible.len();
+    for (i, node) in visible.into_iter().enumerate() {
+        let (branch, continuation) = if i + 1 == count {
+            (glyphs.last, glyphs.space)
+        } else {
+            (glyphs.branch, glyphs.pipe)
+        };
+        let child_prefix = format!("{prefix}{continuation}");
+        match node {
+            DoctorNode::Check(check) => {
+                let check_lines = check_lines(check, options);
+                let mut rows = check_lines.iter().flat_map(|line| line.split('\n'));
+                if let Some(first) = rows.next() {
+                    lines.push(format!("{prefix}{branch}{first}"));
+                }
+                lines.extend(rows.map(|row| format!("{child_prefix}{row}")));
+            }
+            DoctorNode::Section { title, children } => {
+                lines.push(format!("{prefix}{branch}{title}"));
+                tree_lines(lines, children, &child_prefix, glyphs, options);
+            }
         }
+    }
+}
 
-        fn tool_checks(&self) -> Vec<DoctorCheck> {
-            vec![
-                DoctorCheck::pass("Test check 1"),
-                DoctorCheck::fail("Test check 2", "This is a failure"),
-            ]
+/// Whether `node` has anything to show; quiet mode hides passing checks and
+/// sections with nothing else in them.
+fn node_visible(node: &DoctorNode, options: &DoctorOptions) -> bool {
+    match node {
+        DoctorNode::Check(check) => !options.quiet || check.severity != Severity::Pass,
+        DoctorNode::Section { children, .. } => {
+            !options.quiet || children.iter().any(|child| node_visible(child, options))
         }
     }
+}
 
-    #[test]
-    fn test_run_doctor() {
-        let tool = TestTool;
-        let exit_code = run_doctor(&tool);
-        // Should return 1 because we have a failing check
-        assert_eq!(exit_code, 1);
+/// Run doctor checks and render the results as JSON.
+///
+/// Returns the exit code (same semantics as `run_doctor`) and a JSON document of
+/// the form:
+///
+/// ```json
+/// {
+///   "tool": "mytool",
+///   "version": "1.0.0",
+///   "checks": [
+///     {
+///       "name": "...",
+///       "passed": true,
+///       "severity": "pass",
+///       "message": null,
+///       "remediation": null,
+///       "duration": null
+///     }
+///   ],
+///   "error": null
+/// }
+/// ```
+///
+/// `duration` is `{"secs": .., "nanos": ..}` for timed checks.
+/// `error` is set, and `checks` empty, when `try_tool_checks` failed.
+/// Checks from every section are flattened into the `checks` array. Nothing is
+/// printed; the caller decides where the document goes.
+///
+/// # Type Parameters
+/// * `T` - A type that implements `DoctorChecks`
+///
+/// # Panics
+/// Panics if the report cannot be serialized, which cannot happen for the
+/// plain data it contains.
+#[cfg(feature = "serde")]
+#[must_use]
+pub fn run_doctor_json<T: DoctorChecks>(tool: &T) -> (i32, String) {
+    let report = collect_doctor_report(tool);
+    (report.exit_code(), report_json(&report))
+}
+
+#[cfg(feature = "serde")]
+fn report_json(report: &DoctorReport) -> String {
+    let checks: Vec<&DoctorCheck> = report.all_checks().collect();
+    let document = serde_json::json!({
+        "tool": report.tool_name,
+        "version": report.version,
+        "checks": checks,
+        "error": report.error,
+    });
+    serde_json::to_string_pretty(&document).expect("doctor report serializes to JSON")
+}
+
+/// Run doctor checks and print the results to stdout in the given format.
+///
+/// - `Human`: the report printed by `run_doctor`
+/// - `Json`: the document returned by `run_doctor_json` (requires the `serde` feature)
+/// - `Plain`: one line per check, `PASS name`, `WARN name: message` or
+///   `FAIL name: message`, with no color or emoji
+///
+/// Returns the same exit code as `run_doctor` regardless of format.
+///
+/// # Type Parameters
+/// * `T` - A type that implements `DoctorChecks`
+pub fn run_doctor_formatted<T: DoctorChecks>(tool: &T, format: OutputFormat) -> i32 {
+    run_doctor_formatted_to(tool, format, &mut io::stdout().lock())
+}
+
+/// Run doctor checks and write the results to `out` in the given format.
+///
+/// Behaves like `run_doctor_formatted`; write errors are ignored.
+///
+/// # Type Parameters
+/// * `T` - A type that implements `DoctorChecks`
+/// * `W` - The output destination
+pub fn run_doctor_formatted_to<T: DoctorChecks, W: Write>(
+    tool: &T,
+    format: OutputFormat,
+    out: &mut W,
+) -> i32 {
+    let report = collect_doctor_report(tool);
+    let _ = match format {
+        OutputFormat::Human => write_report(&report, &DoctorOptions::default(), out),
+        #[cfg(feature = "serde")]
+        OutputFormat::Json => writeln!(out, "{}", report_json(&report)),
+        OutputFormat::Plain => write_plain(&report, false, out),
+    };
+    report.exit_code()
+}
+
+/// Handle a `doctor` subcommand, mirroring `handle_completions_command`.
+///
+/// Runs the checks once and prints the results to stdout in `format`. With
+/// `quiet`, human output is as for [`DoctorOptions::quiet`] and plain output
+/// omits `PASS` lines; JSON output is always complete.
+///
+/// Returns the same exit code as `run_doctor`.
+///
+/// # Examples
+/// ```no_run
+/// use workhelix_cli_common::{OutputFormat, doctor::handle_doctor_command};
+/// # use workhelix_cli_common::{DoctorCheck, DoctorChecks, RepoInfo};
+/// # struct MyTool;
+/// # impl DoctorChecks for MyTool {
+/// #     fn repo_info() -> RepoInfo { RepoInfo::new("myorg", "mytool", "v") }
+/// #     fn current_version() -> &'static str { "1.0.0" }
+/// #     fn tool_checks(&self) -> Vec<DoctorCheck> { Vec::new() }
+/// # }
+///
+/// std::process::exit(handle_doctor_command(&MyTool, OutputFormat::Human, false));
+/// ```
+///
+/// # Type Parameters
+/// * `T` - A type that implements `DoctorChecks`
+#[must_use]
+pub fn handle_doctor_command<T: DoctorChecks>(tool: &T, format: OutputFormat, quiet: bool) -> i32 {
+    doctor_command(tool, format, quiet, &mut io::stdout().lock())
+}
+
+fn doctor_command<T: DoctorChecks, W: Write>(
+    tool: &T,
+    format: OutputFormat,
+    quiet: bool,
+    out: &mut W,
+) -> i32 {
+    let options = DoctorOptions {
+        quiet,
+        ..DoctorOptions::default()
+    };
+    let report = collect_doctor_report(tool);
+    let _ = match format {
+        OutputFormat::Human => write_report(&report, &options, out),
+        #[cfg(feature = "serde")]
+        OutputFormat::Json => writeln!(out, "{}", report_json(&report)),
+        OutputFormat::Plain => write_plain(&report, quiet, out),
+    };
+    report.exit_code_with(&options)
+}
+
+/// Write one grep-friendly line per check, skipping passes when `quiet`.
+fn write_plain<W: Write>(report: &DoctorReport, quiet: bool, out: &mut W) -> io::Result<()> {
+    if let Some(error) = &report.error {
+        return writeln!(out, "ERROR doctor could not run: {}", one_line(error));
+    }
+    for check in report
+        .all_checks()
+        .filter(|check| !quiet || check.severity != Severity::Pass)
+    {
+        let status = match check.severity {
+            Severity::Pass => "PASS",
+            Severity::Warn => "WARN",
+            Severity::Fail => "FAIL",
+        };
+        match &check.message {
+            Some(msg) if check.severity != Severity::Pass => {
+                writeln!(out, "{status} {}: {}", check.name, one_line(msg))?;
+            }
+            _ => writeln!(out, "{status} {}", check.name)?,
+        }
+    }
+    Ok(())
+}
+
+/// Run doctor checks and render the results as `JUnit` XML for CI systems.
+///
+/// Returns the exit code (same semantics as `run_doctor`) and a document with
+/// one `<testsuite>` named after the tool and one `<testcase>` per check. The
+/// `classname` of each test case is the tool name, followed by the section
+/// title for checks in a section (dot-separated for nested tree sections).
+/// Failing checks get a `<failure>` element and warnings a `<system-out>`
+/// note. If `try_tool_checks` failed, the suite holds a single test case with
+/// an `<error>` element. Nothing is printed.
+///
+/// # Type Parameters
+/// * `T` - A type that implements `DoctorChecks`
+#[must_use]
+pub fn run_doctor_junit<T: DoctorChecks>(tool: &T) -> (i32, String) {
+    let report = collect_doctor_report(tool);
+    (report.exit_code(), report_junit(&report))
+}
+
+fn report_junit(report: &DoctorReport) -> String {
+    use std::fmt::Write as _;
+
+    let tool = xml_escape(&report.tool_name);
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    if let Some(error) = &report.error {
+        let error = xml_escape(error);
+        writeln!(
+            xml,
+            "<testsuite name=\"{tool}\" tests=\"1\" failures=\"0\" errors=\"1\">"
+        )
+        .unwrap();
+        writeln!(
+            xml,
+            "  <testcase name=\"doctor\" classname=\"{tool}\">\n    <error message=\"{error}\">{error}</error>\n  </testcase>"
+        )
+        .unwrap();
+        xml.push_str("</testsuite>\n");
+        return xml;
+    }
+
+    let mut cases: Vec<(String, &DoctorCheck)> = report
+        .checks
+        .iter()
+        .map(|check| (tool.clone(), check))
+        .collect();
+    for section in &report.sections {
+        let classname = format!("{tool}.{}", xml_escape(&section.title));
+        cases.extend(
+            section
+                .checks
+                .iter()
+                .map(|check| (classname.clone(), check)),
+        );
+    }
+    junit_tree_cases(&mut cases, &report.tree, &tool);
+    writeln!(
+        xml,
+        "<testsuite name=\"{tool}\" tests=\"{}\" failures=\"{}\" errors=\"0\">",
+        report.all_checks().count(),
+        report.failed
+    )
+    .unwrap();
+    for (classname, check) in cases {
+        let time = check.duration.map_or_else(String::new, |duration| {
+            format!(" time=\"{:.3}\"", duration.as_secs_f64())
+        });
+        write!(
+            xml,
+            "  <testcase name=\"{}\" classname=\"{classname}\"{time}",
+            xml_escape(&check.name)
+        )
+        .unwrap();
+        let message = xml_escape(check.message.as_deref().unwrap_or_default());
+        match check.severity {
+            Severity::Pass => xml.push_str("/>\n"),
+            Severity::Warn => writeln!(
+                xml,
+                ">\n    <system-out>warning: {message}</system-out>\n  </testcase>"
+            )
+            .unwrap(),
+            Severity::Fail => writeln!(
+                xml,
+                ">\n    <failure message=\"{message}\">{message}</failure>\n  </testcase>"
+            )
+            .unwrap(),
+        }
+    }
+    xml.push_str("</testsuite>\n");
+    xml
+}
+
+/// Collect test cases for tree nodes, extending `classname` with each
+/// section title on the way down.
+fn junit_tree_cases<'a>(
+    cases: &mut Vec<(String, &'a DoctorCheck)>,
+    nodes: &'a [DoctorNode],
+    classname: &str,
+) {
+    for node in nodes {
+        match node {
+            DoctorNode::Check(check) => cases.push((classname.to_string(), check)),
+            DoctorNode::Section { title, children } => {
+                let classname = format!("{classname}.{}", xml_escape(title));
+                junit_tree_cases(cases, children, &classname);
+            }
+        }
+    }
+}
+
+/// Escape the XML special characters in text or an attribute value.
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// `emoji` when the terminal can show it (see [`output::emoji_enabled`]),
+/// `ascii` otherwise.
+fn icon(emoji: &'static str, ascii: &'static str) -> &'static str {
+    if output::emoji_enabled() {
+        emoji
+    } else {
+        ascii
+    }
+}
+
+/// Collapse a multi-line message onto a single line.
+fn one_line(msg: &str) -> String {
+    msg.lines().map(str::trim).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run `f` in an environment where the report uses emoji icons.
+    fn with_emoji<R>(f: impl FnOnce() -> R) -> R {
+        let vars = [
+            (output::ASCII_ENV, None),
+            (output::NO_EMOJI_ENV, None),
+            ("LC_ALL", Some("C.UTF-8")),
+            ("WT_SESSION", Some("1")),
+        ];
+        crate::test_env::with_vars(&vars, f)
+    }
+
+    struct TestTool;
+
+    impl DoctorChecks for TestTool {
+        fn repo_info() -> RepoInfo {
+            RepoInfo::new("workhelix", "test-tool", "v")
+        }
+
+        fn current_version() -> &'static str {
+            "1.0.0"
+        }
+
+        fn tool_checks(&self) -> Vec<DoctorCheck> {
+            vec![
+                DoctorCheck::pass("Test check 1"),
+                DoctorCheck::fail("Test check 2", "This is a failure"),
+            ]
+        }
+    }
+
+    #[test]
+    fn test_run_doctor() {
+        let tool = TestTool;
+        let exit_code = run_doctor(&tool);
+        // Should return 1 because we have a failing check
+        assert_eq!(exit_code, 1);
+    }
+
+    struct WarnTool;
+
+    impl DoctorChecks for WarnTool {
+        fn repo_info() -> RepoInfo {
+            RepoInfo::new("workhelix", "warn-tool", "v")
+        }
+
+        fn current_version() -> &'static str {
+            "1.0.0"
+        }
+
+        fn tool_checks(&self) -> Vec<DoctorCheck> {
+            vec![
+                DoctorCheck::pass("Test check 1"),
+                DoctorCheck::warn("Test check 2", "This is a warning"),
+            ]
+        }
+    }
+
+    #[test]
+    fn test_run_doctor_warnings_only() {
+        let tool = WarnTool;
+        let exit_code = run_doctor(&tool);
+        // Warnings alone don't cause failure
+        assert_eq!(exit_code, 0);
+    }
+
+    #[test]
+    fn test_warnings_only_without_fail_on_warning() {
+        let options = DoctorOptions::default();
+        let mut buffer = Vec::new();
+        assert_eq!(
+            with_emoji(|| run_doctor_with_options_to(&WarnTool, &options, &mut buffer)),
+            0
+        );
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("⚠️  1 warning found"));
+    }
+
+    #[test]
+    fn test_warnings_only_with_fail_on_warning() {
+        let options = DoctorOptions {
+            fail_on_warning: true,
+            ..DoctorOptions::default()
+        };
+        let mut buffer = Vec::new();
+        assert_eq!(
+            run_doctor_with_options_to(&WarnTool, &options, &mut buffer),
+            DEFAULT_WARNING_EXIT_CODE
+        );
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("treated as failures"));
+
+        let options = DoctorOptions {
+            fail_on_warning: true,
+            warning_exit_code: 3,
+            ..DoctorOptions::default()
+        };
+        assert_eq!(
+            run_doctor_with_options_to(&WarnTool, &options, &mut Vec::new()),
+            3
+        );
+        // Hard failures still return 1
+        assert_eq!(
+            run_doctor_with_options_to(&TestTool, &options, &mut Vec::new()),
+            1
+        );
+    }
+
+    struct SectionTool;
+
+    impl DoctorChecks for SectionTool {
+        fn repo_info() -> RepoInfo {
+            RepoInfo::new("workhelix", "section-tool", "v")
+        }
+
+        fn current_version() -> &'static str {
+            "1.0.0"
+        }
+
+        fn tool_sections(&self) -> Vec<DoctorSection> {
+            vec![
+                DoctorSection::new("Network", vec![DoctorCheck::pass("API reachable")]),
+                DoctorSection::new(
+                    "Credentials",
+                    vec![
+                        DoctorCheck::pass("Token present"),
+                        DoctorCheck::fail("Token valid", "Token expired"),
+                    ],
+                ),
+            ]
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_run_doctor_json() {
+        let (exit_code, json) = run_doctor_json(&SectionTool);
+        assert_eq!(exit_code, 1);
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["tool"], "section-tool");
+        assert_eq!(value["version"], "1.0.0");
+        let checks = value["checks"].as_array().unwrap();
+        assert_eq!(checks.len(), 3);
+        assert_eq!(checks[2]["name"], "Token valid");
+        assert_eq!(checks[2]["passed"], false);
+        assert_eq!(checks[2]["message"], "Token expired");
+    }
+
+    /// Value of `attribute` on the first `<element ...>` tag in `xml`.
+    fn xml_attribute<'a>(xml: &'a str, element: &str, attribute: &str) -> Option<&'a str> {
+        let start = xml.find(&format!("<{element} "))?;
+        let tag = &xml[start..start + xml[start..].find('>')?];
+        let value = &tag[tag.find(&format!(" {attribute}=\""))? + attribute.len() + 3..];
+        Some(&value[..value.find('"')?])
+    }
+
+    #[test]
+    fn test_run_doctor_junit() {
+        let (exit_code, xml) = run_doctor_junit(&SectionTool);
+        assert_eq!(exit_code, 1);
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuite "));
+        assert_eq!(
+            xml_attribute(&xml, "testsuite", "name"),
+            Some("section-tool")
+        );
+        assert_eq!(xml_attribute(&xml, "testsuite", "tests"), Some("3"));
+        assert_eq!(xml_attribute(&xml, "testsuite", "failures"), Some("1"));
+        assert_eq!(xml.matches("<testcase ").count(), 3);
+        assert_eq!(xml.matches("<failure ").count(), 1);
+        assert!(xml.contains(
+            "  <testcase name=\"Token valid\" classname=\"section-tool.Credentials\">\n    <failure message=\"Token expired\">Token expired</failure>\n  </testcase>\n"
+        ));
+        assert!(xml.trim_end().ends_with("</testsuite>"));
+
+        let (exit_code, xml) = run_doctor_junit(&TestTool);
+        assert_eq!(exit_code, 1);
+        assert!(xml.contains("<testcase name=\"Test check 1\" classname=\"test-tool\"/>"));
+    }
+
+    #[test]
+    fn test_run_doctor_junit_escapes_and_errors() {
+        struct XmlTool;
+
+        impl DoctorChecks for XmlTool {
+            fn repo_info() -> RepoInfo {
+                RepoInfo::new("workhelix", "xml-tool", "v")
+            }
+
+            fn current_version() -> &'static str {
+                "1.0.0"
+            }
+
+            fn tool_checks(&self) -> Vec<DoctorCheck> {
+                vec![
+                    DoctorCheck::fail("<a & b>", "got \"x\" < 'y'"),
+                    DoctorCheck::warn("Cache", "cold"),
+                ]
+            }
+        }
+
+        let (_, xml) = run_doctor_junit(&XmlTool);
+        assert!(xml.contains("name=\"&lt;a &amp; b&gt;\""));
+        assert!(xml.contains(
+            "<failure message=\"got &quot;x&quot; &lt; &apos;y&apos;\">got &quot;x&quot; &lt; &apos;y&apos;</failure>"
+        ));
+        assert!(xml.contains("<system-out>warning: cold</system-out>"));
+        assert_eq!(xml_attribute(&xml, "testsuite", "failures"), Some("1"));
+
+        let report = DoctorReport::setup_failed("xml-tool", "1.0.0", "bad <config>");
+        let xml = report_junit(&report);
+        assert_eq!(xml_attribute(&xml, "testsuite", "errors"), Some("1"));
+        assert!(xml.contains("<error message=\"bad &lt;config&gt;\">"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_handle_doctor_command_json() {
+        let mut buffer = Vec::new();
+        let exit_code = doctor_command(&SectionTool, OutputFormat::Json, true, &mut buffer);
+        assert_eq!(exit_code, 1);
+        let value: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(value["tool"], "section-tool");
+        // Quiet does not trim the JSON document
+        assert_eq!(value["checks"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_handle_doctor_command_quiet() {
+        let mut buffer = Vec::new();
+        let exit_code = doctor_command(&SectionTool, OutputFormat::Plain, true, &mut buffer);
+        assert_eq!(exit_code, 1);
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "FAIL Token valid: Token expired\n"
+        );
+
+        let mut buffer = Vec::new();
+        doctor_command(&SectionTool, OutputFormat::Human, true, &mut buffer);
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("Token valid"));
+        assert!(!text.contains("API reachable"));
+    }
+
+    fn sample_tree() -> Vec<DoctorNode> {
+        vec![DoctorNode::section(
+            "Storage",
+            vec![
+                DoctorNode::section(
+                    "Cache",
+                    vec![
+                        DoctorCheck::pass("Cache dir exists").into(),
+                        DoctorCheck::warn("Cache size", "Over 1 GB").into(),
+                    ],
+                ),
+                DoctorCheck::fail("Data dir writable", "Permission denied").into(),
+            ],
+        )]
+    }
+
+    #[test]
+    fn test_tree_lines_connectors() {
+        with_emoji(|| {
+            let mut lines = Vec::new();
+            let DoctorNode::Section { children, .. } = &sample_tree()[0] else {
+                unreachable!()
+            };
+            tree_lines(
+                &mut lines,
+                children,
+                "",
+                &BOX_TREE,
+                &DoctorOptions::default(),
+            );
+            assert_eq!(
+                lines,
+                [
+                    "├── Cache",
+                    "│   ├── ✅ Cache dir exists",
+                    "│   └── ⚠️  Cache size",
+                    "│          Over 1 GB",
+                    "└── ❌ Data dir writable",
+                    "       Permission denied",
+                ]
+            );
+
+            let mut lines = Vec::new();
+            let quiet = DoctorOptions {
+                quiet: true,
+                ..DoctorOptions::default()
+            };
+            tree_lines(&mut lines, children, "", &PLAIN_TREE, &quiet);
+            assert_eq!(
+                lines,
+                [
+                    "  Cache",
+                    "    ⚠️  Cache size",
+                    "       Over 1 GB",
+                    "  ❌ Data dir writable",
+                    "     Permission denied",
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_report_with_tree() {
+        let report = DoctorReport::new("tool", "1.0.0", vec![DoctorCheck::pass("a")], Vec::new())
+            .with_tree(sample_tree());
+        assert_eq!(report.all_checks().count(), 4);
+        assert_eq!((report.passed, report.failed, report.warnings), (2, 1, 1));
+
+        let xml = report_junit(&report);
+        assert!(xml.contains("classname=\"tool.Storage.Cache\""));
+        assert!(xml.contains("<testcase name=\"Data dir writable\" classname=\"tool.Storage\">"));
+    }
+
+    #[test]
+    fn test_run_doctor_formatted_plain() {
+        let mut buffer = Vec::new();
+        let exit_code = run_doctor_formatted_to(&SectionTool, OutputFormat::Plain, &mut buffer);
+        assert_eq!(exit_code, 1);
+        let text = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("PASS "));
+        assert_eq!(lines[2], "FAIL Token valid: Token expired");
+
+        let mut buffer = Vec::new();
+        assert_eq!(
+            run_doctor_formatted_to(&WarnTool, OutputFormat::Plain, &mut buffer),
+            0
+        );
+        let text = String::from_utf8(buffer).unwrap();
+        assert_eq!(
+            text,
+            "PASS Test check 1\nWARN Test check 2: This is a warning\n"
+        );
+        assert!(!text.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_run_doctor_formatted_human() {
+        let mut buffer = Vec::new();
+        let exit_code = run_doctor_formatted_to(&TestTool, OutputFormat::Human, &mut buffer);
+        assert_eq!(exit_code, 1);
+        assert!(String::from_utf8(buffer).unwrap().contains("health check"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_run_doctor_formatted_json() {
+        let mut buffer = Vec::new();
+        let exit_code = run_doctor_formatted_to(&SectionTool, OutputFormat::Json, &mut buffer);
+        assert_eq!(exit_code, 1);
+        let value: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(value["checks"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_list_doctor_checks() {
+        let names = list_doctor_checks(&TestTool);
+        let expected: Vec<String> = TestTool
+            .tool_checks()
+            .into_iter()
+            .map(|check| check.name)
+            .collect();
+        assert_eq!(names, expected);
+
+        assert_eq!(
+            list_doctor_checks(&SlowTool),
+            ["Sync check", "Slow check", "Fast check", "Panicking check"]
+        );
+        assert_eq!(list_doctor_checks(&SectionTool).len(), 3);
+    }
+
+    struct SlowTool;
+
+    impl DoctorChecks for SlowTool {
+        fn repo_info() -> RepoInfo {
+            RepoInfo::new("workhelix", "slow-tool", "v")
+        }
+
+        fn current_version() -> &'static str {
+            "1.0.0"
+        }
+
+        fn tool_checks(&self) -> Vec<DoctorCheck> {
+            vec![DoctorCheck::pass("Sync check")]
+        }
+
+        fn tool_checks_parallel(&self) -> Vec<PendingCheck> {
+            vec![
+                PendingCheck::new("Slow check", || {
+                    thread::sleep(Duration::from_secs(2));
+                    DoctorCheck::pass("Slow check")
+                }),
+                PendingCheck::new("Fast check", || DoctorCheck::pass("Fast check")),
+                PendingCheck::new("Panicking check", || panic!("boom")),
+            ]
+        }
+
+        fn check_timeout(&self) -> Duration {
+            Duration::from_millis(100)
+        }
+    }
+
+    #[test]
+    fn test_parallel_checks_timeout() {
+        let report = collect_doctor_report(&SlowTool);
+        let checks = &report.checks;
+        let names: Vec<&str> = checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Sync check", "Slow check", "Fast check", "Panicking check"]
+        );
+
+        assert!(checks[0].passed);
+        assert!(!checks[1].passed);
+        assert!(checks[1].message.as_ref().unwrap().contains("timed out"));
+        assert!(checks[2].passed);
+        assert!(!checks[3].passed);
+        assert_eq!(checks[3].message.as_deref(), Some("Check panicked: boom"));
+    }
+
+    #[test]
+    fn test_deadline_skips_unfinished_checks() {
+        struct SlowChecksTool;
+
+        impl DoctorChecks for SlowChecksTool {
+            fn repo_info() -> RepoInfo {
+                RepoInfo::new("workhelix", "slow-checks-tool", "v")
+            }
+
+            fn current_version() -> &'static str {
+                "1.0.0"
+            }
+
+            fn tool_checks_parallel(&self) -> Vec<PendingCheck> {
+                [0, 10, 5000, 5000]
+                    .into_iter()
+                    .map(|millis| {
+                        let name = format!("Sleep {millis}ms");
+                        PendingCheck::new(name.clone(), move || {
+                            thread::sleep(Duration::from_millis(millis));
+                            DoctorCheck::pass(name)
+                        })
+                    })
+                    .collect()
+            }
+
+            fn tool_sections(&self) -> Vec<DoctorSection> {
+                vec![DoctorSection::new(
+                    "Storage",
+                    vec![DoctorCheck::pass("Cache")],
+                )]
+            }
+        }
+
+        let started = Instant::now();
+        let deadline = started + Duration::from_millis(300);
+        let report = collect_report(&SlowChecksTool, Some(deadline));
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let results: Vec<(&str, bool, Option<&str>)> = report
+            .all_checks()
+            .map(|check| (check.name.as_str(), check.passed, check.message.as_deref()))
+            .collect();
+        assert_eq!(
+            results,
+            [
+                ("Sleep 0ms", true, None),
+                ("Sleep 10ms", true, None),
+                ("Sleep 5000ms", false, Some(DEADLINE_MESSAGE)),
+                ("Sleep 5000ms", false, Some(DEADLINE_MESSAGE)),
+                ("Cache", true, None),
+            ]
+        );
+        assert_eq!(report.exit_code(), 1);
+
+        // Sections are skipped only when the deadline passes before they are computed
+        let report = collect_report(&SlowChecksTool, Some(Instant::now()));
+        assert!(report.sections.is_empty());
+        assert_eq!(report.checks.last().unwrap().name, "Remaining checks");
+
+        // A per-check timeout shorter than the deadline is still a timeout
+        let report = collect_report(&SlowTool, Some(Instant::now() + Duration::from_secs(60)));
+        assert!(
+            report.checks[1]
+                .message
+                .as_ref()
+                .unwrap()
+                .contains("timed out")
+        );
+    }
+
+    #[test]
+    fn test_deadline_with_only_parallel_checks() {
+        struct ParallelOnlyTool;
+
+        impl DoctorChecks for ParallelOnlyTool {
+            fn repo_info() -> RepoInfo {
+                RepoInfo::new("workhelix", "parallel-only-tool", "v")
+            }
+
+            fn current_version() -> &'static str {
+                "1.0.0"
+            }
+
+            fn tool_checks_parallel(&self) -> Vec<PendingCheck> {
+                vec![
+                    PendingCheck::new("Fast", || DoctorCheck::pass("Fast")),
+                    PendingCheck::new("Slow", || {
+                        thread::sleep(Duration::from_secs(5));
+                        DoctorCheck::pass("Slow")
+                    }),
+                ]
+            }
+        }
+
+        let deadline = Instant::now() + Duration::from_millis(200);
+        let report = collect_report(&ParallelOnlyTool, Some(deadline));
+        let names: Vec<&str> = report
+            .all_checks()
+            .map(|check| check.name.as_str())
+            .collect();
+        assert_eq!(names, ["Fast", "Slow"]);
+        assert_eq!(report.checks[1].message.as_deref(), Some(DEADLINE_MESSAGE));
+        assert_eq!(report.failed, 1);
+    }
+
+    #[test]
+    fn test_parallel_checks_exit_code() {
+        assert_eq!(run_doctor(&SlowTool), 1);
+    }
+
+    #[test]
+    fn test_run_doctor_to_buffer() {
+        let mut buffer = Vec::new();
+        let exit_code = run_doctor_to(&TestTool, &mut buffer);
+        assert_eq!(exit_code, 1);
+
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("test-tool health check"));
+        assert!(text.contains("Configuration:"));
+        assert!(text.contains("Test check 1"));
+        assert!(text.contains("This is a failure"));
+        assert!(text.contains("1 issue found"));
+    }
+
+    #[test]
+    fn test_run_doctor_to_renders_warnings() {
+        let mut buffer = Vec::new();
+        assert_eq!(with_emoji(|| run_doctor_to(&WarnTool, &mut buffer)), 0);
+
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("⚠️  Test check 2"));
+        assert!(text.contains("This is a warning"));
+        assert!(text.contains("1 warning found"));
+    }
+
+    #[test]
+    fn test_remediation_renders_only_for_failures() {
+        struct FixTool;
+
+        impl DoctorChecks for FixTool {
+            fn repo_info() -> RepoInfo {
+                RepoInfo::new("workhelix", "fix-tool", "v")
+            }
+
+            fn current_version() -> &'static str {
+                "1.0.0"
+            }
+
+            fn tool_checks(&self) -> Vec<DoctorCheck> {
+                let mut warning = DoctorCheck::warn("Cache", "cache is cold");
+                warning.remediation = Some("warm the cache".to_string());
+                vec![
+                    DoctorCheck::fail_with_fix("Config", "config missing", "fix-tool init"),
+                    DoctorCheck::fail("Network", "unreachable"),
+                    warning,
+                ]
+            }
+        }
+
+        let mut buffer = Vec::new();
+        with_emoji(|| run_doctor_to(&FixTool, &mut buffer));
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("  ❌ Config\n     config missing\n     → try: fix-tool init\n"));
+        assert!(text.contains("  ❌ Network\n     unreachable\n  ⚠️  Cache"));
+        assert_eq!(text.matches("→ try:").count(), 1);
+    }
+
+    #[test]
+    fn test_ascii_report() {
+        let vars = [(output::ASCII_ENV, Some("1"))];
+        let mut buffer = Vec::new();
+        crate::test_env::with_vars(&vars, || {
+            run_doctor_to(&TestTool, &mut buffer);
+        });
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.starts_with("test-tool health check\n"));
+        assert!(text.contains("  [PASS] Test check 1\n  [FAIL] Test check 2\n"));
+        assert!(text.contains("[FAIL] 1 issue found"));
+        assert!(!text.contains('❌'));
+    }
+
+    #[test]
+    fn test_show_timings() {
+        struct TimedTool;
+
+        impl DoctorChecks for TimedTool {
+            fn repo_info() -> RepoInfo {
+                RepoInfo::new("workhelix", "timed-tool", "v")
+            }
+
+            fn current_version() -> &'static str {
+                "1.0.0"
+            }
+
+            fn tool_checks(&self) -> Vec<DoctorCheck> {
+                let mut timed = DoctorCheck::pass("Network");
+                timed.duration = Some(Duration::from_millis(123));
+                vec![timed, DoctorCheck::pass("Config")]
+            }
+
+            fn include_system_info(&self) -> bool {
+                false
+            }
+        }
+
+        let options = DoctorOptions {
+            show_timings: true,
+            ..DoctorOptions::default()
+        };
+        let mut buffer = Vec::new();
+        with_emoji(|| run_doctor_with_options_to(&TimedTool, &options, &mut buffer));
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("✅ Network (123ms)\n"));
+        assert!(text.contains("✅ Config\n"));
+
+        let mut buffer = Vec::new();
+        run_doctor_to(&TimedTool, &mut buffer);
+        assert!(!String::from_utf8(buffer).unwrap().contains("123ms"));
+    }
+
+    #[test]
+    fn test_pending_checks_are_timed() {
+        let checks = vec![PendingCheck::new("slow", || {
+            thread::sleep(Duration::from_millis(20));
+            DoctorCheck::pass("slow")
+        })];
+        let results = run_pending_checks(checks, Duration::from_secs(5), None);
+        assert!(results[0].duration.unwrap() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_try_tool_checks_error_is_single_failure() {
+        struct BrokenSetupTool;
+
+        impl DoctorChecks for BrokenSetupTool {
+            fn repo_info() -> RepoInfo {
+                RepoInfo::new("workhelix", "broken-tool", "v")
+            }
+
+            fn current_version() -> &'static str {
+                "1.0.0"
+            }
+
+            fn try_tool_checks(&self) -> Result<Vec<DoctorCheck>, String> {
+                Err("cannot read config directory".to_string())
+            }
+
+            fn tool_sections(&self) -> Vec<DoctorSection> {
+                vec![DoctorSection::new(
+                    "Network",
+                    vec![DoctorCheck::pass("API")],
+                )]
+            }
+        }
+
+        let report = collect_doctor_report(&BrokenSetupTool);
+        assert_eq!(
+            report.error.as_deref(),
+            Some("cannot read config directory")
+        );
+        assert_eq!(report.all_checks().count(), 0);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.exit_code(), 1);
+
+        let mut buffer = Vec::new();
+        assert_eq!(
+            with_emoji(|| run_doctor_to(&BrokenSetupTool, &mut buffer)),
+            1
+        );
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("❌ Doctor could not run: cannot read config directory"));
+        assert!(!text.contains("Network"));
+        assert!(!text.contains("found - see above"));
+    }
+
+    #[test]
+    fn test_system_info_preamble() {
+        struct NoInfoTool;
+
+        impl DoctorChecks for NoInfoTool {
+            fn repo_info() -> RepoInfo {
+                RepoInfo::new("workhelix", "quiet-tool", "v")
+            }
+
+            fn current_version() -> &'static str {
+                "9.9.9"
+            }
+
+            fn include_system_info(&self) -> bool {
+                false
+            }
+        }
+
+        let mut buffer = Vec::new();
+        run_doctor_to(&TestTool, &mut buffer);
+        let text = String::from_utf8(buffer).unwrap();
+        let preamble = text.split("Configuration").next().unwrap();
+        assert!(preamble.contains("1.0.0"));
+        assert!(preamble.contains(&crate::platform::target_triple()));
+
+        let report = collect_doctor_report(&NoInfoTool);
+        assert!(report.system_info.is_none());
+        let mut buffer = Vec::new();
+        run_doctor_to(&NoInfoTool, &mut buffer);
+        assert!(!String::from_utf8(buffer).unwrap().contains("9.9.9"));
+    }
+
+    #[test]
+    fn test_quiet_omits_passing_checks() {
+        let options = DoctorOptions {
+            quiet: true,
+            ..DoctorOptions::default()
+        };
+        let mut buffer = Vec::new();
+        let exit_code = run_doctor_with_options_to(&TestTool, &options, &mut buffer);
+        assert_eq!(exit_code, 1);
+
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(!text.contains("Test check 1"));
+        assert!(!text.contains("health check"));
+        assert!(text.contains("Test check 2"));
+        assert!(text.contains("This is a failure"));
+        assert!(text.contains("1 issue found"));
+    }
+
+    #[test]
+    fn test_quiet_all_passing_prints_single_line() {
+        struct HealthyTool;
+
+        impl DoctorChecks for HealthyTool {
+            fn repo_info() -> RepoInfo {
+                RepoInfo::new("workhelix", "healthy-tool", "v")
+            }
+
+            fn current_version() -> &'static str {
+                "1.0.0"
+            }
+
+            fn tool_checks(&self) -> Vec<DoctorCheck> {
+                vec![DoctorCheck::pass("Check A"), DoctorCheck::pass("Check B")]
+            }
+        }
+
+        let options = DoctorOptions {
+            quiet: true,
+            ..DoctorOptions::default()
+        };
+        let mut buffer = Vec::new();
+        assert_eq!(
+            run_doctor_with_options_to(&HealthyTool, &options, &mut buffer),
+            0
+        );
+
+        let text = String::from_utf8(buffer).unwrap();
+        assert_eq!(text.lines().count(), 1);
+        assert!(text.contains("Everything looks healthy"));
+    }
+
+    #[test]
+    fn test_collect_doctor_report() {
+        let report = collect_doctor_report(&TestTool);
+        assert_eq!(report.tool_name, "test-tool");
+        assert_eq!(report.version, "1.0.0");
+        assert_eq!(report.checks.len(), 2);
+        assert_eq!(report.passed, 1);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.exit_code(), run_doctor(&TestTool));
+
+        let report = collect_doctor_report(&WarnTool);
+        assert_eq!(report.warnings, 1);
+        assert_eq!(report.exit_code(), 0);
+        assert_eq!(report.exit_code(), run_doctor(&WarnTool));
+    }
+
+    #[test]
+    fn test_doctor_report_counts_sections() {
+        let report = collect_doctor_report(&SectionTool);
+        assert!(report.checks.is_empty());
+        assert_eq!(report.all_checks().count(), 3);
+        assert_eq!(report.passed, 2);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.exit_code(), 1);
+    }
+
+    #[test]
+    fn test_run_doctor_sections() {
+        let tool = SectionTool;
+        let exit_code = run_doctor(&tool);
+        // The failure in the second section is aggregated into the summary
+        assert_eq!(exit_code, 1);
+    }
+
+    #[test]
+    fn test_stream_doctor_checks() {
+        let summary =
+            |check: &DoctorCheck| (check.name.clone(), check.passed, check.message.clone());
+        let streamed: Vec<_> = stream_doctor_checks(&TestTool).collect();
+        assert_eq!(
+            streamed.iter().map(summary).collect::<Vec<_>>(),
+            TestTool
+                .tool_checks()
+                .iter()
+                .map(summary)
+                .collect::<Vec<_>>()
+        );
+
+        let report = collect_doctor_report(&SectionTool);
+        assert_eq!(
+            stream_doctor_checks(&SectionTool)
+                .map(|check| check.name)
+                .collect::<Vec<_>>(),
+            report
+                .all_checks()
+                .map(|check| check.name.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    struct AsyncTool;
+
+    #[cfg(feature = "tokio")]
+    impl AsyncDoctorChecks for AsyncTool {
+        fn repo_info() -> RepoInfo {
+            RepoInfo::new("workhelix", "async-tool", "v")
+        }
+
+        fn current_version() -> &'static str {
+            "1.0.0"
+        }
+
+        async fn tool_checks(&self) -> Vec<DoctorCheck> {
+            vec![DoctorCheck::pass("Local check")]
+        }
+
+        fn concurrent_checks(&self) -> Vec<AsyncCheck<'_>> {
+            vec![
+                AsyncCheck::new("Fast", async { DoctorCheck::pass("Fast") }),
+                AsyncCheck::new("Warn", async { DoctorCheck::warn("Warn", "degraded") }),
+                AsyncCheck::new("Broken", async { DoctorCheck::fail("Broken", "down") }),
+                AsyncCheck::new("Slow", async {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    DoctorCheck::pass("Slow")
+                }),
+            ]
+        }
+
+        fn check_timeout(&self) -> Duration {
+            Duration::from_millis(50)
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_collect_doctor_report_async() {
+        let report = collect_doctor_report_async(&AsyncTool).await;
+        let names: Vec<&str> = report.checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Local check", "Fast", "Warn", "Broken", "Slow"]);
+        assert_eq!(report.passed, 2);
+        assert_eq!(report.warnings, 1);
+        assert_eq!(report.failed, 2);
+        assert!(
+            report.checks[4]
+                .message
+                .as_deref()
+                .is_some_and(|m| m.contains("timed out"))
+        );
+        assert_eq!(run_doctor_async(&AsyncTool).await, 1);
     }
 }
@@ -4,6 +4,8 @@
 //! - Shell completion generation
 //! - Health check framework
 //! - License display
+//! - Self-update
+//! - Version output
 //! - Terminal output utilities
 //!
 //! # Example Usage
@@ -24,7 +26,8 @@
 //!
 //! impl DoctorChecks for MyTool {
 //!     fn repo_info() -> RepoInfo {
-//!         RepoInfo::new("myorg", "mytool")
+//!         // Owner and name come from `repository` in Cargo.toml
+//!         workhelix_cli_common::repo_info!()
 //!     }
 //!
 //!     fn current_version() -> &'static str {
@@ -47,9 +50,52 @@
 //! ```
 
 // Re-export main types and traits
+pub use checks::expand_path;
 pub use doctor::DoctorChecks;
 pub use license::LicenseType;
-pub use types::{DoctorCheck, RepoInfo};
+pub use platform::config_path;
+#[cfg(feature = "tokio")]
+pub use types::AsyncCheck;
+pub use types::{
+    CommonError, DoctorCheck, DoctorCheckBuilder, DoctorNode, DoctorSection, ExitCode,
+    PendingCheck, RepoInfo, Severity,
+};
+
+/// Build a [`RepoInfo`] from the calling crate's `Cargo.toml`.
+///
+/// Reads the `repository` field via `env!("CARGO_PKG_REPOSITORY")` at compile
+/// time, so owner and name cannot drift from the manifest. The release tag
+/// prefix defaults to `v`; pass another prefix as the only argument. Pair it
+/// with `env!("CARGO_PKG_VERSION")` for the current version.
+///
+/// # Panics
+///
+/// Panics if the `repository` field is missing or is not a URL of the form
+/// accepted by [`RepoInfo::from_repository_url`].
+///
+/// # Examples
+/// ```
+/// let repo = workhelix_cli_common::repo_info!();
+/// assert_eq!(repo.owner, "tftio");
+///
+/// let repo = workhelix_cli_common::repo_info!("workhelix-cli-common-v");
+/// assert_eq!(repo.tag_prefix, "workhelix-cli-common-v");
+/// ```
+#[macro_export]
+macro_rules! repo_info {
+    () => {
+        $crate::repo_info!("v")
+    };
+    ($tag_prefix:expr) => {
+        $crate::RepoInfo::from_repository_url(env!("CARGO_PKG_REPOSITORY"), $tag_prefix)
+            .expect("Cargo.toml `repository` must be a URL like https://github.com/owner/name")
+    };
+}
+
+// Built-in DoctorCheck constructors
+mod checks;
+// Target triple, OS version and config directory detection
+mod platform;
 
 // Public modules
 pub mod completions;
@@ -57,11 +103,99 @@ pub mod doctor;
 pub mod license;
 pub mod output;
 pub mod types;
+pub mod update;
+pub mod version;
 
 // Re-export commonly used items
-pub use completions::generate_completions;
-pub use doctor::run_doctor;
+pub use completions::{
+    completions_install_path, completions_to_string, detect_shell, generate_completions,
+    generate_completions_named, handle_completions_command, install_completions,
+};
+#[cfg(feature = "serde")]
+pub use doctor::run_doctor_json;
+#[cfg(feature = "tokio")]
+pub use doctor::{AsyncDoctorChecks, collect_doctor_report_async, run_doctor_async};
+pub use doctor::{
+    DoctorOptions, DoctorReport, SystemInfo, collect_doctor_report, handle_doctor_command,
+    list_doctor_checks, run_doctor, run_doctor_formatted, run_doctor_junit, run_doctor_to,
+    run_doctor_with_deadline, run_doctor_with_options, stream_doctor_checks,
+};
 pub use license::display_license;
+#[cfg(feature = "serde")]
+pub use license::display_license_json;
+pub use output::{ColorChoice, OutputFormat};
+#[cfg(feature = "http")]
+pub use update::{UpdateError, UpdateStatus, check_for_update};
+#[cfg(feature = "self-update")]
+pub use update::{UpdateOptions, parse_checksums_file, run_update_native, verify_sha256};
+pub use update::{is_offline, run_update, set_offline};
+pub use version::{handle_version_command, print_version, version_string};
+
+/// Helpers for tests that need to modify process-wide environment variables.
+#[cfg(test)]
+pub(crate) mod test_env {
+    use std::sync::{Mutex, MutexGuard};
+
+    /// Serializes tests that modify environment variables, and tests whose
+    /// assertions depend on variables another test may set.
+    pub static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Serializes tests that toggle offline mode with tests that need the network.
+    static OFFLINE_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Hold [`OFFLINE_LOCK`] for the rest of the test.
+    pub fn offline_lock() -> MutexGuard<'static, ()> {
+        OFFLINE_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Run `f` with `name` set to `value` (or removed for `None`), restoring the
+    /// previous value afterwards.
+    pub fn with_var<R>(name: &str, value: Option<&str>, f: impl FnOnce() -> R) -> R {
+        with_vars(&[(name, value)], f)
+    }
+
+    /// Run `f` with several variables set (or removed for `None`), restoring
+    /// the previous values afterwards.
+    #[allow(unsafe_code)]
+    pub fn with_vars<R>(vars: &[(&str, Option<&str>)], f: impl FnOnce() -> R) -> R {
+        let _guard = ENV_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let previous: Vec<_> = vars
+            .iter()
+            .map(|(name, _)| (*name, std::env::var_os(name)))
+            .collect();
+        // SAFETY: all environment mutation in tests happens while holding
+        // ENV_LOCK, so writes never race each other. Other test threads may
+        // still read the environment without the lock (e.g. rendering tests
+        // calling `std::env::var`); those reads go through std, which guards
+        // the environment with its own lock, so they see either the old or the
+        // new value. Std's lock does not cover C code calling `getenv`
+        // directly on another thread (e.g. during DNS resolution in a network
+        // test); such a read can still race with these writes.
+        unsafe {
+            for (name, value) in vars {
+                match value {
+                    Some(value) => std::env::set_var(name, value),
+                    None => std::env::remove_var(name),
+                }
+            }
+        }
+        let result = f();
+        // SAFETY: see above; ENV_LOCK is still held.
+        unsafe {
+            for (name, value) in previous {
+                match value {
+                    Some(value) => std::env::set_var(name, value),
+                    None => std::env::remove_var(name),
+                }
+            }
+        }
+        result
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -69,7 +203,7 @@ mod tests {
 
     #[test]
     fn test_repo_info_creation() {
-        let repo = RepoInfo::new("workhelix", "test");
+        let repo = RepoInfo::new("workhelix", "test", "v");
         assert_eq!(repo.owner, "workhelix");
         assert_eq!(repo.name, "test");
     }
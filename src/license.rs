@@ -3,6 +3,11 @@
 //! This module provides standardized license information display for common open source licenses.
 
 use crate::output;
+use crate::types::CommonError;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{PoisonError, RwLock};
 
 /// Supported license types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -13,35 +18,492 @@ pub enum LicenseType {
     Apache2,
     /// Creative Commons CC0 1.0 Universal
     CC0,
+    /// GNU General Public License v3.0
+    GPL3,
+    /// GNU Lesser General Public License v3.0
+    LGPL3,
+    /// BSD 2-Clause "Simplified" License
+    BSD2Clause,
+    /// BSD 3-Clause "New" or "Revised" License
+    BSD3Clause,
+    /// Mozilla Public License 2.0
+    MPL2,
+    /// ISC License
+    ISC,
+    /// The Unlicense (public domain dedication)
+    Unlicense,
+    /// A license outside this list, identified by its SPDX identifier (e.g.
+    /// `LicenseRef-Acme-Proprietary`) and described by
+    /// [`register_custom_license`]
+    Custom(&'static str),
 }
 
 impl LicenseType {
+    /// Get every supported license type, in declaration order.
+    ///
+    /// Useful for building `--license-list` style commands without hardcoding
+    /// the variants.
+    #[must_use]
+    pub const fn all() -> &'static [Self] {
+        &[
+            Self::MIT,
+            Self::Apache2,
+            Self::CC0,
+            Self::GPL3,
+            Self::LGPL3,
+            Self::BSD2Clause,
+            Self::BSD3Clause,
+            Self::MPL2,
+            Self::ISC,
+            Self::Unlicense,
+        ]
+    }
+
     /// Parse a license type from a string.
     ///
-    /// Recognizes common variations like "MIT", "Apache-2.0", "CC0-1.0", etc.
+    /// Recognizes common variations like "MIT", "Apache-2.0", "CC0-1.0", "GPLv3",
+    /// "BSD-3", "MPL-2.0", etc. A bare "BSD" is treated as BSD-3-Clause.
+    /// Identifiers registered with [`register_custom_license`] are recognized too.
     #[must_use]
     pub fn parse(s: &str) -> Option<Self> {
         match s.to_uppercase().as_str() {
             "MIT" => Some(Self::MIT),
             "APACHE-2.0" | "APACHE2" | "APACHE" => Some(Self::Apache2),
             "CC0-1.0" | "CC0" => Some(Self::CC0),
-            _ => None,
+            "GPL-3.0-ONLY" | "GPL-3.0" | "GPL-3" | "GPLV3" | "GPL3" | "GPL" => Some(Self::GPL3),
+            "LGPL-3.0-ONLY" | "LGPL-3.0" | "LGPL-3" | "LGPLV3" | "LGPL3" | "LGPL" => {
+                Some(Self::LGPL3)
+            }
+            "BSD-2-CLAUSE" | "BSD-2" | "BSD2" => Some(Self::BSD2Clause),
+            "BSD-3-CLAUSE" | "BSD-3" | "BSD3" | "BSD" => Some(Self::BSD3Clause),
+            "MPL-2.0" | "MPL-2" | "MPL2" | "MPL" => Some(Self::MPL2),
+            "ISC" => Some(Self::ISC),
+            "UNLICENSE" | "THE UNLICENSE" => Some(Self::Unlicense),
+            _ => custom_licenses()
+                .iter()
+                .find(|(spdx, _)| spdx.eq_ignore_ascii_case(s))
+                .map(|(spdx, _)| Self::Custom(spdx)),
         }
     }
 
     /// Get the license name.
+    ///
+    /// This is the canonical SPDX identifier for the license.
     #[must_use]
     pub const fn name(self) -> &'static str {
         match self {
             Self::MIT => "MIT",
             Self::Apache2 => "Apache-2.0",
             Self::CC0 => "CC0-1.0",
+            Self::GPL3 => "GPL-3.0-only",
+            Self::LGPL3 => "LGPL-3.0-only",
+            Self::BSD2Clause => "BSD-2-Clause",
+            Self::BSD3Clause => "BSD-3-Clause",
+            Self::MPL2 => "MPL-2.0",
+            Self::ISC => "ISC",
+            Self::Unlicense => "Unlicense",
+            Self::Custom(spdx) => spdx,
+        }
+    }
+}
+
+/// Description of a license that is not built in, for [`register_custom_license`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CustomLicense {
+    /// Full license name (e.g., "Acme Proprietary License")
+    pub name: &'static str,
+    /// Line shown above the permissions (e.g., "Acme Proprietary License - internal use only:")
+    pub summary: &'static str,
+    /// What the license allows
+    pub permissions: &'static [&'static str],
+    /// What the license requires
+    pub conditions: &'static [&'static str],
+    /// What the license does not provide
+    pub limitations: &'static [&'static str],
+    /// Full legal text, appended by `display_license` when not empty
+    pub text: &'static str,
+}
+
+/// Custom licenses by SPDX identifier, in registration order.
+static CUSTOM_LICENSES: RwLock<Vec<(&'static str, CustomLicense)>> = RwLock::new(Vec::new());
+
+/// Register a license that is not built in, so [`LicenseType::Custom`] with
+/// this identifier renders like the built-in licenses.
+///
+/// Registering an identifier again replaces its description. Built-in licenses
+/// never consult the registry.
+///
+/// # Examples
+/// ```
+/// use workhelix_cli_common::LicenseType;
+/// use workhelix_cli_common::license::{CustomLicense, display_license, register_custom_license};
+///
+/// register_custom_license(
+///     "LicenseRef-Acme",
+///     CustomLicense {
+///         name: "Acme Proprietary License",
+///         summary: "Acme Proprietary License - internal use only:",
+///         permissions: &["Private use"],
+///         conditions: &["Keep confidential"],
+///         limitations: &["Distribution"],
+///         text: "",
+///     },
+/// );
+/// let text = display_license("mytool", LicenseType::Custom("LicenseRef-Acme"));
+/// assert!(text.contains("internal use only"));
+/// ```
+pub fn register_custom_license(spdx: &'static str, summary: CustomLicense) {
+    let mut licenses = CUSTOM_LICENSES
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    match licenses.iter_mut().find(|(id, _)| *id == spdx) {
+        Some(entry) => entry.1 = summary,
+        None => licenses.push((spdx, summary)),
+    }
+}
+
+/// Snapshot of the registered custom licenses.
+fn custom_licenses() -> Vec<(&'static str, CustomLicense)> {
+    CUSTOM_LICENSES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// The registered description of a custom license.
+fn custom_license(spdx: &str) -> Option<CustomLicense> {
+    custom_licenses()
+        .into_iter()
+        .find(|(id, _)| *id == spdx)
+        .map(|(_, license)| license)
+}
+
+/// Lets a `--license` flag take a `LicenseType` directly, with shell
+/// completions offering every SPDX identifier:
+///
+/// ```
+/// use clap::Parser;
+/// use workhelix_cli_common::LicenseType;
+///
+/// #[derive(Parser)]
+/// struct Cli {
+///     #[arg(long, value_enum, ignore_case = true)]
+///     license: LicenseType,
+/// }
+///
+/// let cli = Cli::parse_from(["mytool", "--license", "apache-2.0"]);
+/// assert_eq!(cli.license, LicenseType::Apache2);
+/// ```
+impl clap::ValueEnum for LicenseType {
+    fn value_variants<'a>() -> &'a [Self] {
+        Self::all()
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(clap::builder::PossibleValue::new(self.name()).help(license_data(*self).name))
+    }
+}
+
+/// Machine-readable description of a license.
+///
+/// The permission, condition, and limitation lists follow the vocabulary used by
+/// choosealicense.com. With the `serde` feature enabled this type implements
+/// `serde::Serialize` so tools can emit it as JSON or YAML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LicenseInfo {
+    /// SPDX identifier (e.g., "MIT")
+    pub spdx: &'static str,
+    /// Full license name (e.g., "MIT License")
+    pub name: &'static str,
+    /// What the license allows
+    pub permissions: Vec<&'static str>,
+    /// What the license requires
+    pub conditions: Vec<&'static str>,
+    /// What the license does not provide
+    pub limitations: Vec<&'static str>,
+}
+
+/// Static license data backing both `LicenseInfo` and the prose summary.
+struct LicenseData {
+    name: &'static str,
+    heading: &'static str,
+    permissions: &'static [&'static str],
+    conditions: &'static [&'static str],
+    limitations: &'static [&'static str],
+}
+
+const BASIC_PERMISSIONS: &[&str] = &[
+    "Commercial use",
+    "Modification",
+    "Distribution",
+    "Private use",
+];
+
+const PATENT_PERMISSIONS: &[&str] = &[
+    "Commercial use",
+    "Modification",
+    "Distribution",
+    "Patent use",
+    "Private use",
+];
+
+const BASIC_LIMITATIONS: &[&str] = &["Liability", "Warranty"];
+
+const TRADEMARK_LIMITATIONS: &[&str] = &["Liability", "Trademark use", "Warranty"];
+
+fn license_data(license: LicenseType) -> LicenseData {
+    match license {
+        LicenseType::MIT => LicenseData {
+            name: "MIT License",
+            heading: "MIT License - A permissive license that allows:",
+            permissions: BASIC_PERMISSIONS,
+            conditions: &["License and copyright notice"],
+            limitations: BASIC_LIMITATIONS,
+        },
+        LicenseType::Apache2 => LicenseData {
+            name: "Apache License 2.0",
+            heading: "Apache License 2.0 - A permissive license that allows:",
+            permissions: PATENT_PERMISSIONS,
+            conditions: &["License and copyright notice", "State changes"],
+            limitations: TRADEMARK_LIMITATIONS,
+        },
+        LicenseType::CC0 => LicenseData {
+            name: "Creative Commons Zero v1.0 Universal",
+            heading: "Creative Commons CC0 1.0 Universal - No Copyright (public domain dedication):",
+            permissions: &[
+                "No rights reserved",
+                "Can be used for any purpose",
+                "No attribution required",
+            ],
+            conditions: &[],
+            limitations: &["Liability", "Patent use", "Trademark use", "Warranty"],
+        },
+        LicenseType::GPL3 => LicenseData {
+            name: "GNU General Public License v3.0",
+            heading: "GNU GPL v3.0 - A strong copyleft license that allows:",
+            permissions: PATENT_PERMISSIONS,
+            conditions: &[
+                "Disclose source",
+                "License and copyright notice",
+                "Same license",
+                "State changes",
+            ],
+            limitations: BASIC_LIMITATIONS,
+        },
+        LicenseType::LGPL3 => LicenseData {
+            name: "GNU Lesser General Public License v3.0",
+            heading: "GNU LGPL v3.0 - A weak copyleft license that allows:",
+            permissions: PATENT_PERMISSIONS,
+            conditions: &[
+                "Disclose source",
+                "License and copyright notice",
+                "Same license (library)",
+                "State changes",
+            ],
+            limitations: BASIC_LIMITATIONS,
+        },
+        LicenseType::BSD2Clause => LicenseData {
+            name: "BSD 2-Clause \"Simplified\" License",
+            heading: "BSD 2-Clause License - A permissive license that allows:",
+            permissions: BASIC_PERMISSIONS,
+            conditions: &["License and copyright notice"],
+            limitations: BASIC_LIMITATIONS,
+        },
+        LicenseType::BSD3Clause => LicenseData {
+            name: "BSD 3-Clause \"New\" or \"Revised\" License",
+            heading: "BSD 3-Clause License - A permissive license that allows:",
+            permissions: BASIC_PERMISSIONS,
+            conditions: &[
+                "License and copyright notice",
+                "No endorsement using contributor names",
+            ],
+            limitations: BASIC_LIMITATIONS,
+        },
+        LicenseType::MPL2 => LicenseData {
+            name: "Mozilla Public License 2.0",
+            heading: "Mozilla Public License 2.0 - A file-level copyleft license that allows:",
+            permissions: PATENT_PERMISSIONS,
+            conditions: &[
+                "Disclose source",
+                "License and copyright notice",
+                "Same license (file)",
+            ],
+            limitations: TRADEMARK_LIMITATIONS,
+        },
+        LicenseType::ISC => LicenseData {
+            name: "ISC License",
+            heading: "ISC License - A permissive license that allows:",
+            permissions: BASIC_PERMISSIONS,
+            conditions: &["License and copyright notice"],
+            limitations: BASIC_LIMITATIONS,
+        },
+        LicenseType::Unlicense => LicenseData {
+            name: "The Unlicense",
+            heading: "The Unlicense - Public domain dedication with no conditions:",
+            permissions: &[
+                "Released into the public domain",
+                "Copy, modify, publish, use, compile, sell, or distribute",
+                "No conditions of any kind",
+            ],
+            conditions: &[],
+            limitations: BASIC_LIMITATIONS,
+        },
+        LicenseType::Custom(spdx) => custom_license_data(spdx),
+    }
+}
+
+/// License data for a custom license, from the registry if it was registered.
+fn custom_license_data(spdx: &'static str) -> LicenseData {
+    custom_license(spdx).map_or(
+        LicenseData {
+            name: spdx,
+            heading: "Unregistered custom license:",
+            permissions: &[],
+            conditions: &[],
+            limitations: &[],
+        },
+        |custom| LicenseData {
+            name: custom.name,
+            heading: custom.summary,
+            permissions: custom.permissions,
+            conditions: custom.conditions,
+            limitations: custom.limitations,
+        },
+    )
+}
+
+/// Get structured, machine-readable information about a license.
+///
+/// # Examples
+/// ```
+/// use workhelix_cli_common::license::{license_info, LicenseType};
+///
+/// let info = license_info(LicenseType::MIT);
+/// assert_eq!(info.spdx, "MIT");
+/// assert!(info.permissions.contains(&"Commercial use"));
+/// ```
+#[must_use]
+pub fn license_info(license: LicenseType) -> LicenseInfo {
+    let data = license_data(license);
+    LicenseInfo {
+        spdx: license.name(),
+        name: data.name,
+        permissions: data.permissions.to_vec(),
+        conditions: data.conditions.to_vec(),
+        limitations: data.limitations.to_vec(),
+    }
+}
+
+/// Broad license families used by the compatibility matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LicenseFamily {
+    PublicDomain,
+    Permissive,
+    Apache,
+    FileCopyleft,
+    WeakCopyleft,
+    StrongCopyleft,
+    Custom,
+}
+
+const fn license_family(license: LicenseType) -> LicenseFamily {
+    match license {
+        LicenseType::CC0 | LicenseType::Unlicense => LicenseFamily::PublicDomain,
+        LicenseType::MIT | LicenseType::BSD2Clause | LicenseType::BSD3Clause | LicenseType::ISC => {
+            LicenseFamily::Permissive
         }
+        LicenseType::Apache2 => LicenseFamily::Apache,
+        LicenseType::MPL2 => LicenseFamily::FileCopyleft,
+        LicenseType::LGPL3 => LicenseFamily::WeakCopyleft,
+        LicenseType::GPL3 => LicenseFamily::StrongCopyleft,
+        LicenseType::Custom(_) => LicenseFamily::Custom,
+    }
+}
+
+/// Check whether code under license `a` may be included in a work distributed under license `b`.
+///
+/// The check is directional: `is_compatible(MIT, Apache2)` is `true` (MIT code can
+/// ship inside an Apache-2.0 project) while `is_compatible(Apache2, MIT)` is `false`
+/// (Apache-2.0 carries patent and notice terms MIT cannot pass on).
+///
+/// The matrix is deliberately conservative:
+///
+/// | `a` (included)            | may be distributed under `b` = ...              |
+/// |---------------------------|-------------------------------------------------|
+/// | CC0, Unlicense            | anything                                        |
+/// | MIT, BSD-2/3-Clause, ISC  | anything except a public-domain dedication      |
+/// | Apache-2.0                | Apache-2.0, MPL-2.0, LGPL-3.0, GPL-3.0          |
+/// | MPL-2.0                   | MPL-2.0, LGPL-3.0, GPL-3.0 (secondary licenses) |
+/// | LGPL-3.0                  | LGPL-3.0, GPL-3.0                               |
+/// | GPL-3.0                   | GPL-3.0 only                                    |
+/// | custom                    | itself only                                     |
+///
+/// A license is always compatible with itself. This is a best-effort helper for
+/// flagging obvious problems, not legal advice; consult counsel for real
+/// distribution decisions.
+#[must_use]
+pub fn is_compatible(a: LicenseType, b: LicenseType) -> bool {
+    use LicenseFamily::{
+        Apache, FileCopyleft, Permissive, PublicDomain, StrongCopyleft, WeakCopyleft,
+    };
+
+    a == b
+        || matches!(
+            (license_family(a), license_family(b)),
+            (PublicDomain, _)
+                | (
+                    Permissive,
+                    Permissive | Apache | FileCopyleft | WeakCopyleft | StrongCopyleft
+                )
+                | (Apache, FileCopyleft | WeakCopyleft | StrongCopyleft)
+                | (FileCopyleft, WeakCopyleft | StrongCopyleft)
+                | (WeakCopyleft, StrongCopyleft)
+        )
+}
+
+/// Get the canonical legal text of a license.
+///
+/// Returns only the license body, with no tool name, summary, color, or
+/// copyright line, so it can be embedded verbatim (e.g. in a NOTICE file).
+/// For a custom license this is the registered text, which may be empty.
+#[must_use]
+pub fn license_full_text(license: LicenseType) -> &'static str {
+    match license {
+        LicenseType::Custom(spdx) => custom_license(spdx).map_or("", |custom| custom.text),
+        _ => license_text_file(license),
+    }
+}
+
+/// Get the license text embedded from `src/licenses/{spdx}.txt`.
+///
+/// The texts are compiled in with `include_str!`, so adding a license means
+/// dropping its text into that directory and adding a match arm here.
+/// Custom licenses have no embedded file, so this is empty for them; see
+/// [`license_full_text`].
+#[must_use]
+pub const fn license_text_file(license: LicenseType) -> &'static str {
+    match license {
+        LicenseType::MIT => include_str!("licenses/MIT.txt"),
+        LicenseType::Apache2 => include_str!("licenses/Apache-2.0.txt"),
+        LicenseType::CC0 => include_str!("licenses/CC0-1.0.txt"),
+        LicenseType::GPL3 => include_str!("licenses/GPL-3.0-only.txt"),
+        LicenseType::LGPL3 => include_str!("licenses/LGPL-3.0-only.txt"),
+        LicenseType::BSD2Clause => include_str!("licenses/BSD-2-Clause.txt"),
+        LicenseType::BSD3Clause => include_str!("licenses/BSD-3-Clause.txt"),
+        LicenseType::MPL2 => include_str!("licenses/MPL-2.0.txt"),
+        LicenseType::ISC => include_str!("licenses/ISC.txt"),
+        LicenseType::Unlicense => include_str!("licenses/Unlicense.txt"),
+        LicenseType::Custom(_) => "",
     }
 }
 
 /// Display license information for a tool.
 ///
+/// Shows a summary of what the license allows and requires. For MIT,
+/// Apache-2.0, CC0-1.0, and custom licenses registered with a text, the full
+/// license text follows the summary.
+///
 /// # Arguments
 /// * `tool_name` - Name of the tool
 /// * `license` - License type
@@ -50,105 +512,782 @@ impl LicenseType {
 /// Formatted license information string
 #[must_use]
 pub fn display_license(tool_name: &str, license: LicenseType) -> String {
-    let mut output = format!("{tool_name} is licensed under {}\n\n", license.name());
+    render_license(tool_name, license, None)
+}
+
+/// Render license information for a tool as pretty-printed JSON.
+///
+/// The document has the form `{"tool": "mytool", "license": {...}}`, where
+/// `license` is the [`LicenseInfo`] for `license`. Suitable for a
+/// `mytool license --json` command.
+///
+/// # Panics
+/// Panics if the license info cannot be serialized, which cannot happen for the
+/// plain data it contains.
+#[cfg(feature = "serde")]
+#[must_use]
+pub fn display_license_json(tool_name: &str, license: LicenseType) -> String {
+    let document = serde_json::json!({
+        "tool": tool_name,
+        "license": license_info(license),
+    });
+    serde_json::to_string_pretty(&document).expect("license info serializes to JSON")
+}
+
+/// Display license information for a tool, including a copyright line.
+///
+/// For MIT, BSD-style, and ISC licenses the full license text is included with
+/// `Copyright (c) {year} {holder}` placed directly below the license title, where
+/// those licenses expect it. Other copyleft/permissive licenses get the copyright
+/// line right after the "licensed under" preamble, and public-domain dedications
+/// (CC0, Unlicense) get no copyright line at all.
+///
+/// Years outside `1970..=9999` are treated as invalid and omitted, producing
+/// `Copyright (c) {holder}` rather than a nonsensical date.
+///
+/// # Arguments
+/// * `tool_name` - Name of the tool
+/// * `license` - License type
+/// * `holder` - Copyright holder (person or organization)
+/// * `year` - Copyright year
+///
+/// # Returns
+/// Formatted license information string
+#[must_use]
+pub fn display_license_with_copyright(
+    tool_name: &str,
+    license: LicenseType,
+    holder: &str,
+    year: u32,
+) -> String {
+    render_license(tool_name, license, Some(&copyright_line(holder, year)))
+}
 
+/// Licenses whose full text `display_license` appends after the summary.
+fn includes_full_text(license: LicenseType) -> bool {
     match license {
-        LicenseType::MIT => {
-            output.push_str("MIT License - A permissive license that allows:\n");
-            output.push_str("• Commercial use\n");
-            output.push_str("• Modification\n");
-            output.push_str("• Distribution\n");
-            output.push_str("• Private use\n");
-            output.push('\n');
-            output.push_str("Requires:\n");
-            output.push_str("• License and copyright notice\n");
-            output.push('\n');
-            output.push_str("MIT License\n");
-            output.push('\n');
-            output.push_str(
-                "Permission is hereby granted, free of charge, to any person obtaining a copy\n",
-            );
-            output.push_str(
-                "of this software and associated documentation files (the \"Software\"), to deal\n",
-            );
-            output.push_str(
-                "in the Software without restriction, including without limitation the rights\n",
-            );
-            output.push_str(
-                "to use, copy, modify, merge, publish, distribute, sublicense, and/or sell\n",
-            );
-            output.push_str(
-                "copies of the Software, and to permit persons to whom the Software is\n",
-            );
-            output.push_str("furnished to do so, subject to the following conditions:\n");
-            output.push('\n');
-            output.push_str(
-                "The above copyright notice and this permission notice shall be included in all\n",
-            );
-            output.push_str("copies or substantial portions of the Software.\n");
-            output.push('\n');
-            output.push_str(
-                "THE SOFTWARE IS PROVIDED \"AS IS\", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR\n",
-            );
-            output.push_str(
-                "IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,\n",
-            );
-            output.push_str(
-                "FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE\n",
-            );
-            output.push_str(
-                "AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER\n",
-            );
-            output.push_str(
-                "LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,\n",
-            );
-            output.push_str(
-                "OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE\n",
-            );
-            output.push_str("SOFTWARE.\n");
-        }
-        LicenseType::Apache2 => {
-            output.push_str("Apache License 2.0 - A permissive license that allows:\n");
-            output.push_str("• Commercial use\n");
-            output.push_str("• Modification\n");
-            output.push_str("• Distribution\n");
-            output.push_str("• Patent use\n");
-            output.push_str("• Private use\n");
+        LicenseType::MIT | LicenseType::Apache2 | LicenseType::CC0 => true,
+        LicenseType::Custom(_) => !license_full_text(license).is_empty(),
+        _ => false,
+    }
+}
+
+/// Where a license expects its copyright notice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CopyrightPlacement {
+    /// Inside the license text, directly below the title.
+    InText,
+    /// Alongside the license text, outside of it.
+    Preamble,
+    /// Nowhere; the work is dedicated to the public domain.
+    None,
+}
+
+const fn copyright_placement(license: LicenseType) -> CopyrightPlacement {
+    match license {
+        LicenseType::MIT | LicenseType::BSD2Clause | LicenseType::BSD3Clause | LicenseType::ISC => {
+            CopyrightPlacement::InText
+        }
+        LicenseType::Apache2
+        | LicenseType::GPL3
+        | LicenseType::LGPL3
+        | LicenseType::MPL2
+        | LicenseType::Custom(_) => CopyrightPlacement::Preamble,
+        LicenseType::CC0 | LicenseType::Unlicense => CopyrightPlacement::None,
+    }
+}
+
+fn copyright_line(holder: &str, year: u32) -> String {
+    if (1970..=9999).contains(&year) {
+        format!("Copyright (c) {year} {holder}")
+    } else {
+        format!("Copyright (c) {holder}")
+    }
+}
+
+/// Insert a copyright line below the title (first paragraph) of a license text.
+fn insert_copyright(text: &str, copyright: &str) -> String {
+    match text.split_once("\n\n") {
+        Some((title, rest)) => format!("{title}\n\n{copyright}\n\n{rest}"),
+        None => format!("{copyright}\n\n{text}"),
+    }
+}
+
+fn render_license(tool_name: &str, license: LicenseType, copyright: Option<&str>) -> String {
+    use std::fmt::Write;
+
+    let placement = copyright_placement(license);
+    let mut output = format!("{tool_name} is licensed under {}\n", license.name());
+    if let Some(copyright) = copyright {
+        if placement == CopyrightPlacement::Preamble {
+            writeln!(output, "{copyright}").unwrap();
+        }
+    }
+    output.push('\n');
+
+    push_summary(&mut output, license);
+
+    match copyright {
+        Some(copyright) if placement == CopyrightPlacement::InText => {
             output.push('\n');
-            output.push_str("Requires:\n");
-            output.push_str("• License and copyright notice\n");
-            output.push_str("• State changes\n");
+            output.push_str(&insert_copyright(license_full_text(license), copyright));
         }
-        LicenseType::CC0 => {
-            output.push_str("Creative Commons CC0 1.0 Universal - Public domain dedication:\n");
-            output.push_str("• No rights reserved\n");
-            output.push_str("• Can be used for any purpose\n");
-            output.push_str("• No attribution required\n");
+        _ if includes_full_text(license) => {
+            output.push('\n');
+            output.push_str(license_full_text(license));
         }
+        _ => {}
     }
 
     output.push('\n');
 
-    if output::is_tty() {
-        use colored::Colorize;
-        use std::fmt::Write;
+    if let LicenseType::Custom(_) = license {
         writeln!(
             output,
-            "For full license text, see: {}",
-            "LICENSE file in project root".blue().underline()
+            "For full license text, see: LICENSE file in project root"
         )
         .unwrap();
     } else {
-        output.push_str("For full license text, see: LICENSE file in project root\n");
+        let url = format!("https://spdx.org/licenses/{}.html", license.name());
+        let link_text = format!("{} on spdx.org", license.name());
+        writeln!(
+            output,
+            "For full license text, see: LICENSE file in project root or {}",
+            output::hyperlink(&link_text, &url)
+        )
+        .unwrap();
     }
 
     output
 }
 
+/// Append a license's heading, permissions, and conditions to `output`.
+fn push_summary(output: &mut String, license: LicenseType) {
+    let info = license_info(license);
+    output.push_str(license_data(license).heading);
+    output.push('\n');
+    output.push_str(&output::bullet_list(&info.permissions));
+    output.push('\n');
+    if !info.conditions.is_empty() {
+        output.push('\n');
+        output.push_str("Requires:\n");
+        output.push_str(&output::bullet_list(&info.conditions));
+        output.push('\n');
+    }
+}
+
+/// A parsed SPDX license expression such as `MIT OR Apache-2.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LicenseExpr {
+    /// A single license
+    Single(LicenseType),
+    /// A choice: the work may be used under any one of these
+    Or(Vec<Self>),
+    /// A conjunction: the work must be used under all of these
+    And(Vec<Self>),
+}
+
+impl LicenseExpr {
+    /// Every license mentioned in the expression, in order of first appearance.
+    #[must_use]
+    pub fn licenses(&self) -> Vec<LicenseType> {
+        let mut licenses = Vec::new();
+        self.collect_licenses(&mut licenses);
+        licenses
+    }
+
+    fn collect_licenses(&self, licenses: &mut Vec<LicenseType>) {
+        match self {
+            Self::Single(license) => {
+                if !licenses.contains(license) {
+                    licenses.push(*license);
+                }
+            }
+            Self::Or(terms) | Self::And(terms) => {
+                for term in terms {
+                    term.collect_licenses(licenses);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for LicenseExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (terms, operator) = match self {
+            Self::Single(license) => return f.write_str(license.name()),
+            Self::Or(terms) => (terms, " OR "),
+            Self::And(terms) => (terms, " AND "),
+        };
+        for (i, term) in terms.iter().enumerate() {
+            if i > 0 {
+                f.write_str(operator)?;
+            }
+            if matches!(term, Self::Single(_)) {
+                write!(f, "{term}")?;
+            } else {
+                write!(f, "({term})")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse an SPDX license expression like `MIT OR Apache-2.0` or `(MIT AND CC0-1.0)`.
+///
+/// `OR` and `AND` are matched case-insensitively, `AND` binds tighter than
+/// `OR`, and parentheses group sub-expressions. License identifiers accept the
+/// same spellings as [`LicenseType::parse`]. Returns `None` for unknown
+/// licenses, unsupported operators such as `WITH`, or malformed expressions.
+///
+/// # Examples
+/// ```
+/// use workhelix_cli_common::LicenseType;
+/// use workhelix_cli_common::license::{LicenseExpr, parse_spdx_expression};
+///
+/// assert_eq!(
+///     parse_spdx_expression("MIT OR Apache-2.0"),
+///     Some(LicenseExpr::Or(vec![
+///         LicenseExpr::Single(LicenseType::MIT),
+///         LicenseExpr::Single(LicenseType::Apache2),
+///     ]))
+/// );
+/// ```
+#[must_use]
+pub fn parse_spdx_expression(s: &str) -> Option<LicenseExpr> {
+    let spaced = s.replace('(', " ( ").replace(')', " ) ");
+    let tokens: Vec<&str> = spaced.split_whitespace().collect();
+    let mut pos = 0;
+    let expr = parse_or(&tokens, &mut pos)?;
+    (pos == tokens.len()).then_some(expr)
+}
+
+/// `or_expr := and_expr ("OR" and_expr)*`
+fn parse_or(tokens: &[&str], pos: &mut usize) -> Option<LicenseExpr> {
+    parse_operator(tokens, pos, "OR", parse_and, LicenseExpr::Or)
+}
+
+/// `and_expr := primary ("AND" primary)*`
+fn parse_and(tokens: &[&str], pos: &mut usize) -> Option<LicenseExpr> {
+    parse_operator(tokens, pos, "AND", parse_primary, LicenseExpr::And)
+}
+
+/// Parse operands separated by `operator`, wrapping two or more in `combine`.
+fn parse_operator(
+    tokens: &[&str],
+    pos: &mut usize,
+    operator: &str,
+    operand: fn(&[&str], &mut usize) -> Option<LicenseExpr>,
+    combine: fn(Vec<LicenseExpr>) -> LicenseExpr,
+) -> Option<LicenseExpr> {
+    let mut terms = vec![operand(tokens, pos)?];
+    while tokens
+        .get(*pos)
+        .is_some_and(|token| token.eq_ignore_ascii_case(operator))
+    {
+        *pos += 1;
+        terms.push(operand(tokens, pos)?);
+    }
+    if terms.len() == 1 {
+        terms.pop()
+    } else {
+        Some(combine(terms))
+    }
+}
+
+/// `primary := "(" or_expr ")" | license-id`
+fn parse_primary(tokens: &[&str], pos: &mut usize) -> Option<LicenseExpr> {
+    let token = *tokens.get(*pos)?;
+    *pos += 1;
+    if token == "(" {
+        let expr = parse_or(tokens, pos)?;
+        if tokens.get(*pos) != Some(&")") {
+            return None;
+        }
+        *pos += 1;
+        return Some(expr);
+    }
+    LicenseType::parse(token).map(LicenseExpr::Single)
+}
+
+/// Display license information for a tool under an SPDX license expression.
+///
+/// States the expression and whether the licenses are alternatives (`OR`) or
+/// all apply (`AND`), followed by each involved license's summary.
+///
+/// # Examples
+/// ```
+/// use workhelix_cli_common::license::{display_license_expr, parse_spdx_expression};
+///
+/// let expr = parse_spdx_expression("MIT OR Apache-2.0").unwrap();
+/// let text = display_license_expr("mytool", &expr);
+/// assert!(text.starts_with("mytool is licensed under MIT OR Apache-2.0"));
+/// ```
+#[must_use]
+pub fn display_license_expr(tool_name: &str, expr: &LicenseExpr) -> String {
+    let mut output = format!("{tool_name} is licensed under {expr}\n");
+    match expr {
+        LicenseExpr::Single(_) => {}
+        LicenseExpr::Or(_) => output.push_str("You may use it under any one of these licenses.\n"),
+        LicenseExpr::And(_) => output.push_str("You must comply with all of these licenses.\n"),
+    }
+    for license in expr.licenses() {
+        output.push('\n');
+        push_summary(&mut output, license);
+    }
+    output
+}
+
+/// Comment syntax used for an SPDX header line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommentStyle {
+    /// `// ...`, as in Rust, C++, Go and JavaScript
+    DoubleSlash,
+    /// `# ...`, as in shell, Python, TOML and YAML
+    Hash,
+    /// `/* ... */`, as in C and CSS
+    Block,
+}
+
+/// Tag marking an SPDX license header line.
+const SPDX_TAG: &str = "SPDX-License-Identifier:";
+
+/// Build an SPDX license header line for a source file, without a trailing newline.
+///
+/// # Examples
+/// ```
+/// use workhelix_cli_common::LicenseType;
+/// use workhelix_cli_common::license::{CommentStyle, spdx_header};
+///
+/// assert_eq!(
+///     spdx_header(LicenseType::MIT, CommentStyle::DoubleSlash),
+///     "// SPDX-License-Identifier: MIT"
+/// );
+/// ```
+#[must_use]
+pub fn spdx_header(license: LicenseType, comment_style: CommentStyle) -> String {
+    let id = license.name();
+    match comment_style {
+        CommentStyle::DoubleSlash => format!("// {SPDX_TAG} {id}"),
+        CommentStyle::Hash => format!("# {SPDX_TAG} {id}"),
+        CommentStyle::Block => format!("/* {SPDX_TAG} {id} */"),
+    }
+}
+
+/// Check whether a source file already starts with an SPDX license header.
+///
+/// Only the leading block of comments (after an optional `#!` line and blank
+/// lines) is searched, so an identifier mentioned later in the code does not
+/// count. Any license identifier is accepted.
+#[must_use]
+pub fn has_spdx_header(content: &str) -> bool {
+    content
+        .lines()
+        .map(str::trim)
+        .take_while(|line| {
+            line.is_empty() || ["//", "#", "/*", "*"].iter().any(|c| line.starts_with(c))
+        })
+        .any(|line| line.contains(SPDX_TAG))
+}
+
+/// Generate a plain-text NOTICE file for bundled third-party components.
+///
+/// Lists every component with its license, then gives each distinct license
+/// once: which components use it, what it permits and requires, and its full
+/// legal text. The output contains no color or terminal formatting, so it can
+/// be written to disk as is.
+///
+/// # Examples
+/// ```
+/// use workhelix_cli_common::LicenseType;
+/// use workhelix_cli_common::license::generate_notice;
+///
+/// let notice = generate_notice(&[("serde", LicenseType::MIT), ("clap", LicenseType::MIT)]);
+/// assert!(notice.contains("Used by: serde, clap"));
+/// ```
+#[must_use]
+pub fn generate_notice(entries: &[(&str, LicenseType)]) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::from("THIRD-PARTY NOTICES\n\n");
+    output.push_str("This software includes the following third-party components:\n\n");
+    let mut licenses: Vec<LicenseType> = Vec::new();
+    for (component, license) in entries {
+        writeln!(output, "  - {component}: {}", license.name()).unwrap();
+        if !licenses.contains(license) {
+            licenses.push(*license);
+        }
+    }
+
+    for license in licenses {
+        let info = license_info(license);
+        let users: Vec<&str> = entries
+            .iter()
+            .filter(|(_, l)| *l == license)
+            .map(|(component, _)| *component)
+            .collect();
+        output.push('\n');
+        output.push_str(NOTICE_SEPARATOR);
+        writeln!(output, "\n{} ({})", info.name, info.spdx).unwrap();
+        writeln!(output, "Used by: {}", users.join(", ")).unwrap();
+        writeln!(output, "Permits: {}", info.permissions.join(", ")).unwrap();
+        if !info.conditions.is_empty() {
+            writeln!(output, "Requires: {}", info.conditions.join(", ")).unwrap();
+        }
+        output.push('\n');
+        output.push_str(license_full_text(license).trim_matches('\n'));
+        output.push('\n');
+    }
+    output
+}
+
+/// Rule between the license sections of a NOTICE file.
+const NOTICE_SEPARATOR: &str =
+    "--------------------------------------------------------------------------------";
+
+/// How a license file differs from the canonical text of its declared license.
+#[derive(Debug)]
+pub enum LicenseMismatch {
+    /// The license file could not be read
+    Unreadable {
+        /// Path of the license file
+        path: PathBuf,
+        /// Why the file could not be read
+        source: io::Error,
+    },
+    /// The normalized texts differ
+    Differs {
+        /// Index of the first differing word in the normalized text
+        word: usize,
+        /// Line of the file (1-based) holding the first differing word; one past
+        /// the last line if the file ends early
+        line: usize,
+        /// Canonical text starting at the mismatch
+        expected: String,
+        /// File text starting at the mismatch
+        found: String,
+    },
+}
+
+impl fmt::Display for LicenseMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreadable { path, source } => {
+                write!(f, "Cannot read {}: {source}", path.display())
+            }
+            Self::Differs {
+                word,
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "license text differs at word {} (line {line}):\n- {expected}\n+ {found}",
+                word + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LicenseMismatch {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Unreadable { source, .. } => Some(source),
+            Self::Differs { .. } => None,
+        }
+    }
+}
+
+impl From<LicenseMismatch> for CommonError {
+    fn from(e: LicenseMismatch) -> Self {
+        match e {
+            LicenseMismatch::Unreadable { source, .. } => Self::Io(source),
+            LicenseMismatch::Differs {
+                line,
+                expected,
+                found,
+                ..
+            } => Self::LicenseMismatch {
+                line,
+                expected,
+                found,
+            },
+        }
+    }
+}
+
+/// Number of words of context reported on each side of a [`LicenseMismatch`].
+const MISMATCH_CONTEXT_WORDS: usize = 8;
+
+/// Check that a license file matches the canonical text of `license`.
+///
+/// Both texts are normalized before comparing: lines starting with
+/// `Copyright` are removed (they name the holder, not the license) and all
+/// whitespace is collapsed, so rewrapped lines and indentation don't matter.
+///
+/// # Errors
+/// Returns [`LicenseMismatch::Unreadable`] if the file cannot be read, or
+/// [`LicenseMismatch::Differs`] describing the first differing word and the
+/// line it is on; its `Display` form is a short `-`/`+` diff. Both convert
+/// into [`CommonError`] with `?`.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::Path;
+/// use workhelix_cli_common::LicenseType;
+/// use workhelix_cli_common::license::verify_license_file;
+///
+/// if let Err(mismatch) = verify_license_file(Path::new("LICENSE"), LicenseType::MIT) {
+///     eprintln!("LICENSE does not match MIT: {mismatch}");
+/// }
+/// ```
+pub fn verify_license_file(path: &Path, license: LicenseType) -> Result<(), LicenseMismatch> {
+    let contents = std::fs::read_to_string(path).map_err(|source| LicenseMismatch::Unreadable {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    compare_license_text(license_full_text(license), &contents)
+}
+
+/// Compare two license texts after normalization, reporting the first difference.
+fn compare_license_text(canonical: &str, actual: &str) -> Result<(), LicenseMismatch> {
+    let expected = normalized_words(canonical);
+    let found = normalized_words(actual);
+    let Some(word) = (0..expected.len().max(found.len()))
+        .find(|&i| expected.get(i).map(|w| w.1) != found.get(i).map(|w| w.1))
+    else {
+        return Ok(());
+    };
+    let context = |words: &[(usize, &str)]| {
+        let end = (word + MISMATCH_CONTEXT_WORDS).min(words.len());
+        if word >= end {
+            "<end of text>".to_string()
+        } else {
+            let words: Vec<&str> = words[word..end].iter().map(|w| w.1).collect();
+            words.join(" ")
+        }
+    };
+    let line = found
+        .get(word)
+        .map_or_else(|| actual.lines().count() + 1, |w| w.0);
+    Err(LicenseMismatch::Differs {
+        word,
+        line,
+        expected: context(&expected),
+        found: context(&found),
+    })
+}
+
+/// Split a license text into words with their 1-based line numbers, skipping
+/// copyright lines.
+fn normalized_words(text: &str) -> Vec<(usize, &str)> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim_start().starts_with("Copyright"))
+        .flat_map(|(index, line)| line.split_whitespace().map(move |word| (index + 1, word)))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_spdx_expression_or() {
+        let expr = parse_spdx_expression("MIT OR Apache-2.0").unwrap();
+        assert_eq!(
+            expr,
+            LicenseExpr::Or(vec![
+                LicenseExpr::Single(LicenseType::MIT),
+                LicenseExpr::Single(LicenseType::Apache2),
+            ])
+        );
+        assert_eq!(parse_spdx_expression("mit or apache-2.0"), Some(expr));
+    }
+
+    #[test]
+    fn test_parse_spdx_expression_and_parens() {
+        let expr = parse_spdx_expression("(MIT AND CC0-1.0)").unwrap();
+        assert_eq!(
+            expr,
+            LicenseExpr::And(vec![
+                LicenseExpr::Single(LicenseType::MIT),
+                LicenseExpr::Single(LicenseType::CC0),
+            ])
+        );
+
+        // AND binds tighter than OR
+        let expr = parse_spdx_expression("ISC OR MIT AND (Apache-2.0 OR MPL-2.0)").unwrap();
+        assert_eq!(expr.to_string(), "ISC OR (MIT AND (Apache-2.0 OR MPL-2.0))");
+        assert_eq!(
+            expr.licenses(),
+            [
+                LicenseType::ISC,
+                LicenseType::MIT,
+                LicenseType::Apache2,
+                LicenseType::MPL2
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_spdx_expression_invalid() {
+        assert_eq!(
+            parse_spdx_expression("MIT"),
+            Some(LicenseExpr::Single(LicenseType::MIT))
+        );
+        assert_eq!(parse_spdx_expression(""), None);
+        assert_eq!(parse_spdx_expression("MIT OR"), None);
+        assert_eq!(parse_spdx_expression("(MIT OR ISC"), None);
+        assert_eq!(parse_spdx_expression("MIT ISC"), None);
+        assert_eq!(parse_spdx_expression("MIT OR Proprietary"), None);
+        assert_eq!(
+            parse_spdx_expression("GPL-3.0 WITH Classpath-exception-2.0"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_display_license_expr() {
+        let expr = parse_spdx_expression("MIT OR Apache-2.0").unwrap();
+        let text = display_license_expr("tool", &expr);
+        assert!(text.starts_with("tool is licensed under MIT OR Apache-2.0\n"));
+        assert!(text.contains("any one of these licenses"));
+        assert!(text.contains("MIT License - A permissive license"));
+        assert!(text.contains("Apache License 2.0 - A permissive license"));
+
+        let expr = parse_spdx_expression("(MIT AND CC0-1.0)").unwrap();
+        let text = display_license_expr("tool", &expr);
+        assert!(text.contains("comply with all of these licenses"));
+    }
+
+    #[test]
+    fn test_verify_license_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("LICENSE");
+
+        // Copyright line and rewrapped whitespace are ignored
+        let text = insert_copyright(
+            license_text_file(LicenseType::MIT),
+            "Copyright (c) 2025 Workhelix",
+        )
+        .replace("copy\nof this", "copy of\n  this");
+        std::fs::write(&path, text).unwrap();
+        verify_license_file(&path, LicenseType::MIT).unwrap();
+
+        std::fs::write(
+            &path,
+            license_text_file(LicenseType::MIT).replace("free of charge", "for a fee"),
+        )
+        .unwrap();
+        let mismatch = verify_license_file(&path, LicenseType::MIT).unwrap_err();
+        let LicenseMismatch::Differs {
+            word,
+            line,
+            expected,
+            found,
+        } = &mismatch
+        else {
+            panic!("expected a text mismatch, got {mismatch:?}");
+        };
+        assert_eq!(*word, 6);
+        assert!(expected.starts_with("free of charge"));
+        assert!(found.starts_with("for a fee"));
+        assert_eq!(*line, 3);
+        assert!(mismatch.to_string().contains("differs at word 7"));
+        assert_eq!(
+            mismatch.to_string(),
+            "license text differs at word 7 (line 3):\n\
+             - free of charge, to any person obtaining a\n\
+             + for a fee, to any person obtaining a"
+        );
+
+        assert!(verify_license_file(&path, LicenseType::Apache2).is_err());
+    }
+
+    #[test]
+    fn test_spdx_header_comment_styles() {
+        assert_eq!(
+            spdx_header(LicenseType::MIT, CommentStyle::DoubleSlash),
+            "// SPDX-License-Identifier: MIT"
+        );
+        assert_eq!(
+            spdx_header(LicenseType::Apache2, CommentStyle::Hash),
+            "# SPDX-License-Identifier: Apache-2.0"
+        );
+        assert_eq!(
+            spdx_header(LicenseType::GPL3, CommentStyle::Block),
+            "/* SPDX-License-Identifier: GPL-3.0-only */"
+        );
+        for style in [
+            CommentStyle::DoubleSlash,
+            CommentStyle::Hash,
+            CommentStyle::Block,
+        ] {
+            let file = format!(
+                "{}\n\nfn main() {{}}\n",
+                spdx_header(LicenseType::ISC, style)
+            );
+            assert!(has_spdx_header(&file), "{style:?}");
+        }
+    }
+
+    #[test]
+    fn test_has_spdx_header() {
+        assert!(has_spdx_header(
+            "#!/bin/sh\n# Copyright 2025 Workhelix\n# SPDX-License-Identifier: MIT\necho hi\n"
+        ));
+        assert!(has_spdx_header(
+            "/*\n * Copyright 2025 Workhelix\n * SPDX-License-Identifier: MIT\n */\n"
+        ));
+        assert!(!has_spdx_header("fn main() {}\n"));
+        assert!(!has_spdx_header(""));
+        // Only the leading comment block counts
+        assert!(!has_spdx_header(
+            "use std::io;\n// SPDX-License-Identifier: MIT\n"
+        ));
+    }
+
+    #[test]
+    fn test_verify_license_file_truncated_and_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("LICENSE");
+        std::fs::write(&path, "MIT License").unwrap();
+        let mismatch = verify_license_file(&path, LicenseType::MIT).unwrap_err();
+        assert!(matches!(
+            mismatch,
+            LicenseMismatch::Differs { word: 2, line: 2, ref found, .. } if found == "<end of text>"
+        ));
+
+        let missing = dir.path().join("missing");
+        assert!(matches!(
+            verify_license_file(&missing, LicenseType::MIT),
+            Err(LicenseMismatch::Unreadable { ref source, .. })
+                if source.kind() == std::io::ErrorKind::NotFound
+        ));
+    }
+
+    #[test]
+    fn test_license_mismatch_into_common_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("LICENSE");
+        std::fs::write(&path, "MIT License").unwrap();
+        let verify = |path: &Path| -> Result<(), CommonError> {
+            verify_license_file(path, LicenseType::MIT)?;
+            Ok(())
+        };
+
+        let err = verify(&path).unwrap_err();
+        assert!(matches!(
+            err,
+            CommonError::LicenseMismatch { line: 2, ref found, .. } if found == "<end of text>"
+        ));
+        assert!(matches!(
+            verify(&dir.path().join("missing")).unwrap_err(),
+            CommonError::Io(ref e) if e.kind() == std::io::ErrorKind::NotFound
+        ));
+    }
+
     #[test]
     fn test_license_type_from_str() {
         assert_eq!(LicenseType::parse("MIT"), Some(LicenseType::MIT));
@@ -160,11 +1299,157 @@ mod tests {
         assert_eq!(LicenseType::parse("unknown"), None);
     }
 
+    #[test]
+    fn test_register_custom_license() {
+        const ACME: LicenseType = LicenseType::Custom("LicenseRef-Acme-Test");
+        let unregistered = display_license("mytool", ACME);
+        assert!(unregistered.contains("Unregistered custom license:"));
+
+        register_custom_license(
+            "LicenseRef-Acme-Test",
+            CustomLicense {
+                name: "Acme Proprietary License",
+                summary: "Acme Proprietary License - internal use only:",
+                permissions: &["Private use"],
+                conditions: &["Keep confidential"],
+                limitations: &["Distribution", "Warranty"],
+                text: "ACME PROPRIETARY LICENSE\n\nAll rights reserved.\n",
+            },
+        );
+        assert_eq!(LicenseType::parse("licenseref-acme-test"), Some(ACME));
+        assert_eq!(ACME.name(), "LicenseRef-Acme-Test");
+
+        let text = display_license("mytool", ACME);
+        assert!(text.starts_with("mytool is licensed under LicenseRef-Acme-Test\n"));
+        assert!(text.contains("Acme Proprietary License - internal use only:"));
+        assert!(text.contains("Private use"));
+        assert!(text.contains("Requires:"));
+        assert!(text.contains("Keep confidential"));
+        assert!(text.contains("All rights reserved."));
+        assert!(!text.contains("spdx.org"));
+
+        let info = license_info(ACME);
+        assert_eq!(info.name, "Acme Proprietary License");
+        assert_eq!(info.limitations, ["Distribution", "Warranty"]);
+        assert!(is_compatible(ACME, ACME));
+        assert!(is_compatible(LicenseType::CC0, ACME));
+        assert!(!is_compatible(ACME, LicenseType::MIT));
+        assert!(!LicenseType::all().contains(&ACME));
+    }
+
+    #[test]
+    fn test_license_type_all() {
+        // Exhaustive match: adding a variant fails to compile here until it is
+        // also given a position in `LicenseType::all()`.
+        let position = |license: LicenseType| match license {
+            LicenseType::MIT => 0,
+            LicenseType::Apache2 => 1,
+            LicenseType::CC0 => 2,
+            LicenseType::GPL3 => 3,
+            LicenseType::LGPL3 => 4,
+            LicenseType::BSD2Clause => 5,
+            LicenseType::BSD3Clause => 6,
+            LicenseType::MPL2 => 7,
+            LicenseType::ISC => 8,
+            LicenseType::Unlicense => 9,
+            LicenseType::Custom(_) => unreachable!("custom licenses are not built in"),
+        };
+
+        let all = LicenseType::all();
+        assert_eq!(all.len(), 10);
+        for (i, license) in all.iter().enumerate() {
+            assert_eq!(position(*license), i);
+            assert_eq!(LicenseType::parse(license.name()), Some(*license));
+        }
+    }
+
+    #[test]
+    fn test_license_type_parse_aliases() {
+        assert_eq!(LicenseType::parse("GPLv3"), Some(LicenseType::GPL3));
+        assert_eq!(LicenseType::parse("gpl-3.0"), Some(LicenseType::GPL3));
+        assert_eq!(LicenseType::parse("LGPL-3.0"), Some(LicenseType::LGPL3));
+        assert_eq!(LicenseType::parse("BSD"), Some(LicenseType::BSD3Clause));
+        assert_eq!(LicenseType::parse("BSD-3"), Some(LicenseType::BSD3Clause));
+        assert_eq!(LicenseType::parse("bsd-2"), Some(LicenseType::BSD2Clause));
+        assert_eq!(LicenseType::parse("MPL-2.0"), Some(LicenseType::MPL2));
+        assert_eq!(LicenseType::parse("isc"), Some(LicenseType::ISC));
+    }
+
+    #[test]
+    fn test_license_type_parse_round_trip() {
+        for license in [
+            LicenseType::GPL3,
+            LicenseType::LGPL3,
+            LicenseType::BSD2Clause,
+            LicenseType::BSD3Clause,
+            LicenseType::MPL2,
+            LicenseType::ISC,
+        ] {
+            assert_eq!(LicenseType::parse(license.name()), Some(license));
+        }
+    }
+
     #[test]
     fn test_license_type_name() {
         assert_eq!(LicenseType::MIT.name(), "MIT");
         assert_eq!(LicenseType::Apache2.name(), "Apache-2.0");
         assert_eq!(LicenseType::CC0.name(), "CC0-1.0");
+        assert_eq!(LicenseType::GPL3.name(), "GPL-3.0-only");
+        assert_eq!(LicenseType::LGPL3.name(), "LGPL-3.0-only");
+        assert_eq!(LicenseType::BSD2Clause.name(), "BSD-2-Clause");
+        assert_eq!(LicenseType::BSD3Clause.name(), "BSD-3-Clause");
+        assert_eq!(LicenseType::MPL2.name(), "MPL-2.0");
+        assert_eq!(LicenseType::ISC.name(), "ISC");
+    }
+
+    #[test]
+    fn test_license_info() {
+        let info = license_info(LicenseType::Apache2);
+        assert_eq!(info.spdx, "Apache-2.0");
+        assert_eq!(info.name, "Apache License 2.0");
+        assert!(info.permissions.contains(&"Patent use"));
+        assert!(info.conditions.contains(&"State changes"));
+        assert!(info.limitations.contains(&"Trademark use"));
+    }
+
+    #[test]
+    fn test_display_license_uses_license_info() {
+        let output = crate::test_env::with_var("NO_COLOR", Some("1"), || {
+            display_license("test-tool", LicenseType::GPL3)
+        });
+        for item in license_info(LicenseType::GPL3)
+            .permissions
+            .iter()
+            .chain(&license_info(LicenseType::GPL3).conditions)
+        {
+            assert!(output.contains(&format!("• {item}\n")));
+        }
+    }
+
+    #[test]
+    fn test_is_compatible_mit_apache() {
+        assert!(is_compatible(LicenseType::MIT, LicenseType::Apache2));
+        assert!(!is_compatible(LicenseType::Apache2, LicenseType::MIT));
+    }
+
+    #[test]
+    fn test_is_compatible_gpl() {
+        assert!(is_compatible(LicenseType::MIT, LicenseType::GPL3));
+        assert!(is_compatible(LicenseType::Apache2, LicenseType::GPL3));
+        assert!(is_compatible(LicenseType::LGPL3, LicenseType::GPL3));
+        assert!(!is_compatible(LicenseType::GPL3, LicenseType::MIT));
+        assert!(!is_compatible(LicenseType::GPL3, LicenseType::LGPL3));
+        assert!(!is_compatible(LicenseType::GPL3, LicenseType::Apache2));
+    }
+
+    #[test]
+    fn test_is_compatible_public_domain_and_self() {
+        for license in LicenseType::all() {
+            assert!(is_compatible(LicenseType::CC0, *license));
+            assert!(is_compatible(LicenseType::Unlicense, *license));
+            assert!(is_compatible(*license, *license));
+        }
+        assert!(!is_compatible(LicenseType::MIT, LicenseType::CC0));
     }
 
     #[test]
@@ -182,6 +1467,9 @@ mod tests {
         assert!(output.contains("test-tool"));
         assert!(output.contains("Apache"));
         assert!(output.contains("Patent use"));
+        assert!(output.contains("https://spdx.org/licenses/Apache-2.0.html"));
+        assert!(output.contains("LICENSE file in project root or Apache-2.0 on spdx.org"));
+        assert!(output.contains("APPENDIX: How to apply"));
     }
 
     #[test]
@@ -190,5 +1478,171 @@ mod tests {
         assert!(output.contains("test-tool"));
         assert!(output.contains("CC0"));
         assert!(output.contains("No rights reserved"));
+        assert!(output.contains("No Copyright"));
+        assert!(output.contains("Statement of Purpose"));
+    }
+
+    #[test]
+    fn test_license_full_text() {
+        let text = license_full_text(LicenseType::Apache2);
+        assert!(text.contains("Licensed under the Apache License"));
+        assert!(license_full_text(LicenseType::MIT).starts_with("MIT License"));
+        assert!(license_full_text(LicenseType::GPL3).contains("GNU GENERAL PUBLIC LICENSE"));
+        assert!(license_full_text(LicenseType::CC0).contains("CC0 1.0 Universal"));
+    }
+
+    #[test]
+    fn test_generate_notice_deduplicates_license_text() {
+        let notice = generate_notice(&[
+            ("alpha", LicenseType::MIT),
+            ("beta", LicenseType::Apache2),
+            ("gamma", LicenseType::MIT),
+        ]);
+        assert!(notice.contains("  - alpha: MIT\n  - beta: Apache-2.0\n  - gamma: MIT\n"));
+        assert_eq!(notice.matches("Permission is hereby granted").count(), 1);
+        assert_eq!(
+            notice
+                .matches("TERMS AND CONDITIONS FOR USE, REPRODUCTION")
+                .count(),
+            1
+        );
+        assert!(notice.contains("Used by: alpha, gamma\n"));
+        assert!(notice.contains("Used by: beta\n"));
+        assert!(notice.find("MIT License (MIT)") < notice.find("Apache License"));
+        assert!(!notice.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_license_value_enum_completions() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        #[command(name = "license-cli")]
+        struct Cli {
+            #[arg(long, value_enum)]
+            license: LicenseType,
+        }
+
+        let script = crate::completions::completions_to_string::<Cli>(clap_complete::Shell::Bash);
+        assert!(script.contains("MIT Apache-2.0 CC0-1.0"));
+        for license in LicenseType::all() {
+            assert!(script.contains(license.name()), "{}", license.name());
+        }
+
+        let cli = Cli::try_parse_from(["license-cli", "--license", "MPL-2.0"]).unwrap();
+        assert_eq!(cli.license, LicenseType::MPL2);
+        assert!(Cli::try_parse_from(["license-cli", "--license", "WTFPL"]).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_display_license_json() {
+        let json = display_license_json("test-tool", LicenseType::Apache2);
+        assert!(json.contains("\"spdx\""));
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["tool"], "test-tool");
+        assert_eq!(value["license"]["spdx"], "Apache-2.0");
+        assert_eq!(
+            value["license"],
+            serde_json::to_value(license_info(LicenseType::Apache2)).unwrap()
+        );
+        assert_eq!(serde_json::to_string_pretty(&value).unwrap(), json);
+    }
+
+    #[test]
+    fn test_license_text_file_embedded() {
+        for &license in LicenseType::all() {
+            let text = license_text_file(license);
+            assert!(!text.trim().is_empty(), "{}", license.name());
+            assert_eq!(text, license_full_text(license));
+        }
+    }
+
+    #[test]
+    fn test_license_full_text_is_plain() {
+        let text = license_full_text(LicenseType::MIT);
+        assert!(!text.contains('\x1b'));
+        assert!(!text.contains("licensed under"));
+        assert!(!text.contains("Copyright (c)"));
+    }
+
+    #[test]
+    fn test_display_license_with_copyright_mit() {
+        let output =
+            display_license_with_copyright("test-tool", LicenseType::MIT, "Workhelix", 2024);
+        assert_eq!(output.matches("Copyright (c) 2024 Workhelix").count(), 1);
+        assert!(output.contains("MIT License\n\nCopyright (c) 2024 Workhelix\n\nPermission"));
+    }
+
+    #[test]
+    fn test_display_license_with_copyright_bsd() {
+        let output =
+            display_license_with_copyright("test-tool", LicenseType::BSD3Clause, "Workhelix", 2024);
+        assert_eq!(output.matches("Copyright (c) 2024 Workhelix").count(), 1);
+        assert!(
+            output
+                .contains("BSD 3-Clause License\n\nCopyright (c) 2024 Workhelix\n\nRedistribution")
+        );
+    }
+
+    #[test]
+    fn test_display_license_with_copyright_preamble() {
+        let output =
+            display_license_with_copyright("test-tool", LicenseType::Apache2, "Workhelix", 2024);
+        assert_eq!(output.matches("Copyright (c) 2024 Workhelix").count(), 1);
+        assert!(output.starts_with(
+            "test-tool is licensed under Apache-2.0\nCopyright (c) 2024 Workhelix\n\n"
+        ));
+    }
+
+    #[test]
+    fn test_display_license_with_copyright_cc0() {
+        let output =
+            display_license_with_copyright("test-tool", LicenseType::CC0, "Workhelix", 2024);
+        assert!(!output.contains("Copyright (c)"));
+        assert_eq!(output, display_license("test-tool", LicenseType::CC0));
+    }
+
+    #[test]
+    fn test_display_license_with_copyright_invalid_year() {
+        let output = display_license_with_copyright("test-tool", LicenseType::MIT, "Workhelix", 42);
+        assert_eq!(output.matches("Copyright (c) Workhelix").count(), 1);
+        assert!(!output.contains("42"));
+    }
+
+    #[test]
+    fn test_unlicense() {
+        assert_eq!(
+            LicenseType::parse("Unlicense"),
+            Some(LicenseType::Unlicense)
+        );
+        assert_eq!(
+            LicenseType::parse("unlicense"),
+            Some(LicenseType::Unlicense)
+        );
+        assert_eq!(LicenseType::Unlicense.name(), "Unlicense");
+
+        let output = display_license("test-tool", LicenseType::Unlicense);
+        assert!(output.contains("test-tool is licensed under Unlicense"));
+        assert!(output.contains("public domain"));
+        assert!(!output.contains("Requires:"));
+        assert!(
+            license_full_text(LicenseType::Unlicense).contains("released into the public domain")
+        );
+    }
+
+    #[test]
+    fn test_display_license_gpl3() {
+        let output = display_license("test-tool", LicenseType::GPL3);
+        assert!(output.contains("GPL-3.0-only"));
+        assert!(output.contains("Disclose source"));
+        assert!(output.contains("Same license"));
+    }
+
+    #[test]
+    fn test_display_license_bsd3() {
+        let output = display_license("test-tool", LicenseType::BSD3Clause);
+        assert!(output.contains("BSD-3-Clause"));
+        assert!(output.contains("No endorsement"));
     }
 }
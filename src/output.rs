@@ -1,8 +1,241 @@
 //! Output utilities for consistent terminal formatting.
 
-use colored::Colorize;
 use is_terminal::IsTerminal;
-use std::io;
+use std::fmt::{self, Write as _};
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{OnceLock, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+pub use colored::Color;
+
+/// How a command presents its results, e.g. from a `--format` flag.
+///
+/// Derives `clap::ValueEnum`, so it can be used directly as a flag type:
+/// `#[arg(long, value_enum, default_value_t = OutputFormat::Human)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Colored, emoji-decorated output for people
+    #[default]
+    Human,
+    /// A JSON document for other programs
+    #[cfg(feature = "serde")]
+    Json,
+    /// One grep-friendly line per result, with no color or emoji
+    Plain,
+}
+
+/// Colors used by the output functions.
+///
+/// The default theme uses green for success, red for errors, yellow for
+/// warnings, blue for info and cyan for headers.
+///
+/// # Examples
+/// ```
+/// use workhelix_cli_common::output::{self, Color, Theme};
+///
+/// output::set_theme(Theme {
+///     success: Color::BrightGreen,
+///     header: Color::Magenta,
+///     ..Theme::default()
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Color of success messages
+    pub success: Color,
+    /// Color of error messages
+    pub error: Color,
+    /// Color of warning messages
+    pub warning: Color,
+    /// Color of info messages
+    pub info: Color,
+    /// Color of headers and table keys
+    pub header: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            success: Color::Green,
+            error: Color::Red,
+            warning: Color::Yellow,
+            info: Color::Blue,
+            header: Color::Cyan,
+        }
+    }
+}
+
+/// The active theme, initialized to [`Theme::default`] on first use.
+static THEME: OnceLock<RwLock<Theme>> = OnceLock::new();
+
+/// Set the color theme used by all output functions.
+///
+/// This is typically called once at startup; later calls replace the theme.
+pub fn set_theme(theme: Theme) {
+    *THEME
+        .get_or_init(RwLock::default)
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = theme;
+}
+
+/// Get the active color theme.
+#[must_use]
+pub fn theme() -> Theme {
+    *THEME
+        .get_or_init(RwLock::default)
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Whether status messages may use emoji icons; see [`set_emoji_enabled`].
+static EMOJI_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Environment variable that disables emoji icons when set to a non-empty value.
+pub const NO_EMOJI_ENV: &str = "WH_NO_EMOJI";
+
+/// Enable or disable emoji icons in [`success`], [`error`], [`warning`] and [`info`].
+///
+/// When disabled, colored output uses ASCII markers such as `[OK]` and
+/// `[ERROR]` in place of the icons, for terminals and logs that render emoji
+/// poorly. Emoji are enabled by default.
+pub fn set_emoji_enabled(enabled: bool) {
+    EMOJI_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether emoji icons are used: enabled via [`set_emoji_enabled`], not
+/// disabled by a non-empty `WH_NO_EMOJI` environment variable, and
+/// [`unicode_supported`] by the terminal.
+#[must_use]
+pub fn emoji_enabled() -> bool {
+    EMOJI_ENABLED.load(Ordering::Relaxed) && !env_flag(NO_EMOJI_ENV) && unicode_supported()
+}
+
+/// Whether all decoration is disabled; see [`set_plain_mode`].
+static PLAIN_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable plain mode, e.g. from a `--plain` flag.
+///
+/// Plain mode turns off all decoration across the crate for output that is
+/// logged to files: color, emoji, Unicode glyphs, hyperlinks, and spinner and
+/// progress bar animation. It takes precedence over every other setting: a
+/// [`ColorChoice`] of `Always`, `CLICOLOR_FORCE` and [`set_emoji_enabled`] are
+/// ignored while it is on. `NO_COLOR` and `WH_ASCII` only ever remove
+/// decoration, so they still apply when plain mode is off. Plain mode is off
+/// by default.
+pub fn set_plain_mode(enabled: bool) {
+    PLAIN_MODE.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether plain mode is enabled; see [`set_plain_mode`].
+#[must_use]
+pub fn plain_mode() -> bool {
+    PLAIN_MODE.load(Ordering::Relaxed)
+}
+
+/// Environment variable that restricts all output to ASCII when set to a
+/// non-empty value.
+pub const ASCII_ENV: &str = "WH_ASCII";
+
+/// Whether the terminal can be expected to render non-ASCII symbols.
+///
+/// This decides between emoji, spinner and bullet glyphs and their ASCII
+/// fallbacks (`[OK]`, `|/-\`, `*`) throughout the crate, including the doctor
+/// report. It is `false` in [`plain_mode`] or when `WH_ASCII` is set.
+/// Otherwise, on Windows it is
+/// `true` only in terminals known to handle Unicode (Windows Terminal,
+/// `ConEmu`, VS Code, or any terminal that sets `TERM`), since the classic
+/// console renders emoji as boxes. Elsewhere it is `true` unless the locale
+/// (`LC_ALL`, `LC_CTYPE` or `LANG`) names a charset other than UTF-8.
+#[must_use]
+pub fn unicode_supported() -> bool {
+    if plain_mode() || env_flag(ASCII_ENV) {
+        return false;
+    }
+    if cfg!(windows) {
+        ["WT_SESSION", "ConEmuANSI", "TERM_PROGRAM", "TERM"]
+            .iter()
+            .any(|var| env_flag(var))
+    } else {
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()));
+        locale_is_unicode(locale.as_deref())
+    }
+}
+
+/// Whether a locale such as `en_US.UTF-8` uses a Unicode charset.
+///
+/// Locales without an explicit charset (`C`, `POSIX`, `en_US`) are assumed to
+/// be Unicode, as modern terminals are even when the locale is unset.
+fn locale_is_unicode(locale: Option<&str>) -> bool {
+    let Some((_, charset)) = locale.and_then(|locale| locale.split_once('.')) else {
+        return true;
+    };
+    let charset = charset.split('@').next().unwrap_or_default();
+    charset.eq_ignore_ascii_case("utf-8") || charset.eq_ignore_ascii_case("utf8")
+}
+
+/// Whether an environment variable is set to a non-empty value.
+fn env_flag(name: &str) -> bool {
+    std::env::var_os(name).is_some_and(|value| !value.is_empty())
+}
+
+/// `unicode` if the terminal supports it, `ascii` otherwise.
+fn glyph(unicode: &'static str, ascii: &'static str) -> &'static str {
+    if unicode_supported() { unicode } else { ascii }
+}
+
+/// When output is colored, e.g. from a `--color` flag.
+///
+/// Derives `clap::ValueEnum`, so it can be used directly as a flag type:
+/// `#[arg(long, value_enum, default_value_t = ColorChoice::Auto)]`, then passed
+/// to [`set_color_choice`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+#[repr(u8)]
+pub enum ColorChoice {
+    /// Color when writing to a terminal, following `NO_COLOR` and `CLICOLOR_FORCE`
+    #[default]
+    Auto,
+    /// Always color, even when piped
+    Always,
+    /// Never color
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to color output going to a stream with the given TTY state.
+    fn resolve(self, tty: bool) -> bool {
+        match self {
+            Self::Auto => colorize_with_env(tty),
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+/// The active [`ColorChoice`], stored as its discriminant.
+static COLOR_CHOICE: AtomicU8 = AtomicU8::new(ColorChoice::Auto as u8);
+
+/// Set when every output function colors its output.
+///
+/// `Always` and `Never` override both TTY detection and the `NO_COLOR` /
+/// `CLICOLOR_FORCE` environment variables. The default is `Auto`.
+pub fn set_color_choice(choice: ColorChoice) {
+    COLOR_CHOICE.store(choice as u8, Ordering::Relaxed);
+}
+
+/// Get the active [`ColorChoice`].
+#[must_use]
+pub fn color_choice() -> ColorChoice {
+    match COLOR_CHOICE.load(Ordering::Relaxed) {
+        1 => ColorChoice::Always,
+        2 => ColorChoice::Never,
+        _ => ColorChoice::Auto,
+    }
+}
 
 /// Check if stdout is a TTY (terminal).
 ///
@@ -13,69 +246,1086 @@ pub fn is_tty() -> bool {
     io::stdout().is_terminal()
 }
 
+/// Decide whether output should be colored.
+///
+/// Output is never colored in [`plain_mode`]. Otherwise a [`ColorChoice`] of
+/// `Always` or `Never` set with [`set_color_choice`] decides outright, and
+/// under `Auto` the `NO_COLOR` and `CLICOLOR_FORCE` conventions apply:
+/// - `NO_COLOR` set to a non-empty value disables color, even on a TTY
+/// - `CLICOLOR_FORCE` set to anything other than `0` forces color, even when piped
+/// - otherwise color is used only when stdout is a TTY
+#[must_use]
+pub fn should_colorize() -> bool {
+    !plain_mode() && color_choice().resolve(is_tty())
+}
+
+/// Check if stderr is a TTY (terminal).
+///
+/// Stdout may be piped while stderr is still a terminal, so messages written to
+/// stderr should be formatted based on this rather than [`is_tty`].
+#[must_use]
+pub fn is_stderr_tty() -> bool {
+    io::stderr().is_terminal()
+}
+
+/// Like [`should_colorize`], but based on whether stderr is a TTY.
+fn should_colorize_stderr() -> bool {
+    !plain_mode() && color_choice().resolve(is_stderr_tty())
+}
+
+/// Apply the `NO_COLOR` and `CLICOLOR_FORCE` conventions to a TTY state.
+fn colorize_with_env(tty: bool) -> bool {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let force = std::env::var_os("CLICOLOR_FORCE").is_some_and(|v| !v.is_empty() && v != "0");
+    colorize_decision(no_color, force, tty)
+}
+
+/// Combine the color environment conventions with the TTY state.
+const fn colorize_decision(no_color: bool, force: bool, tty: bool) -> bool {
+    if no_color { false } else { force || tty }
+}
+
 /// Format a success message with green checkmark.
 ///
-/// Returns colored output if stdout is a TTY, plain text otherwise.
+/// Returns colored output when [`should_colorize`] allows it, plain text otherwise.
 #[must_use]
 pub fn success(msg: &str) -> String {
-    if is_tty() {
-        format!("{} {}", "✅".green(), msg.green())
-    } else {
-        format!("[OK] {msg}")
-    }
+    Message::new(Level::Success, msg, should_colorize()).to_string()
 }
 
 /// Format an error message with red X.
 ///
-/// Returns colored output if stdout is a TTY, plain text otherwise.
+/// Returns colored output when [`should_colorize`] allows it, plain text otherwise.
 #[must_use]
 pub fn error(msg: &str) -> String {
-    if is_tty() {
-        format!("{} {}", "❌".red(), msg.red().bold())
-    } else {
-        format!("[ERROR] {msg}")
-    }
+    Message::new(Level::Error, msg, should_colorize()).to_string()
+}
+
+/// Print an error message to stderr.
+///
+/// Formatting is based on whether stderr is a TTY, so it stays colored when
+/// stdout is piped but stderr is still a terminal.
+pub fn eprint_error(msg: &str) {
+    eprintln!(
+        "{}",
+        Message::new(Level::Error, msg, should_colorize_stderr())
+    );
 }
 
 /// Format a warning message with yellow warning sign.
 ///
-/// Returns colored output if stdout is a TTY, plain text otherwise.
+/// Returns colored output when [`should_colorize`] allows it, plain text otherwise.
 #[must_use]
 pub fn warning(msg: &str) -> String {
-    if is_tty() {
-        format!("{} {}", "⚠️".yellow(), msg.yellow())
-    } else {
-        format!("[WARNING] {msg}")
-    }
+    Message::new(Level::Warning, msg, should_colorize()).to_string()
+}
+
+/// Print a warning message to stderr.
+///
+/// Formatting is based on whether stderr is a TTY, like [`eprint_error`].
+pub fn eprint_warning(msg: &str) {
+    eprintln!(
+        "{}",
+        Message::new(Level::Warning, msg, should_colorize_stderr())
+    );
 }
 
 /// Format an info message with blue info sign.
 ///
-/// Returns colored output if stdout is a TTY, plain text otherwise.
+/// Returns colored output when [`should_colorize`] allows it, plain text otherwise.
 #[must_use]
 pub fn info(msg: &str) -> String {
-    if is_tty() {
-        format!("{} {}", "ℹ️".blue(), msg.blue())
-    } else {
-        format!("[INFO] {msg}")
-    }
+    Message::new(Level::Info, msg, should_colorize()).to_string()
 }
 
 /// Format a header with separator line.
 ///
-/// Returns colored output if stdout is a TTY, plain text otherwise.
+/// Titles wider than `width` are wrapped at word boundaries. A single word
+/// wider than `width` is kept whole, and the separator grows to match it, so
+/// the separator is never shorter than the title.
+///
+/// Returns colored output when [`should_colorize`] allows it, plain text otherwise.
 #[must_use]
 pub fn header(title: &str, width: usize) -> String {
-    if is_tty() {
-        format!("{}\n{}", title.bold().cyan(), "=".repeat(width).cyan())
+    Header {
+        title,
+        width,
+        colorize: should_colorize(),
+    }
+    .to_string()
+}
+
+/// Format a header whose separator spans the terminal width.
+///
+/// Like [`header`], with the width taken from [`terminal_width`], which falls
+/// back to [`DEFAULT_TERMINAL_WIDTH`].
+#[must_use]
+pub fn header_default(title: &str) -> String {
+    header(title, terminal_width())
+}
+
+/// Format a header whose separator spans the terminal width.
+#[deprecated(note = "renamed to `header_default`")]
+#[must_use]
+pub fn header_auto(title: &str) -> String {
+    header_default(title)
+}
+
+/// Get the width of the terminal attached to stdout, in columns.
+///
+/// Returns 80 when stdout is not a terminal or its size cannot be determined.
+#[must_use]
+pub fn terminal_width() -> usize {
+    terminal_size::terminal_size_of(io::stdout())
+        .map_or(DEFAULT_TERMINAL_WIDTH, |(width, _)| usize::from(width.0))
+}
+
+/// Fallback width used when the terminal size is unknown.
+pub const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+/// Truncate a string to fit within `width` display columns.
+///
+/// Truncation happens on grapheme boundaries, so multibyte characters and
+/// combining sequences are never split, and an ellipsis (`…`) is appended when
+/// anything was removed. Strings that already fit are returned unchanged.
+#[must_use]
+pub fn truncate_to_width(s: &str, width: usize) -> String {
+    const ELLIPSIS: &str = "…";
+
+    if s.width() <= width {
+        return s.to_string();
+    }
+    let Some(available) = width.checked_sub(ELLIPSIS.width()) else {
+        return String::new();
+    };
+
+    let mut out = String::new();
+    let mut used = 0;
+    for grapheme in s.graphemes(true) {
+        let w = grapheme.width();
+        if used + w > available {
+            break;
+        }
+        used += w;
+        out.push_str(grapheme);
+    }
+    out.push_str(ELLIPSIS);
+    out
+}
+
+/// Shorten a string to at most `max` display columns by replacing its middle
+/// with an ellipsis (`…`), e.g. `/Users/…/config.toml`.
+///
+/// Useful for paths, where both the start and the file name matter. The head
+/// and tail are cut on grapheme boundaries, with the tail getting any odd
+/// column. Strings that already fit are returned unchanged.
+#[must_use]
+pub fn truncate_middle(s: &str, max: usize) -> String {
+    const ELLIPSIS: &str = "…";
+
+    if s.width() <= max {
+        return s.to_string();
+    }
+    let Some(available) = max.checked_sub(ELLIPSIS.width()) else {
+        return String::new();
+    };
+    let head_width = available / 2;
+    let tail_width = available - head_width;
+
+    let mut head = String::new();
+    let mut used = 0;
+    for grapheme in s.graphemes(true) {
+        used += grapheme.width();
+        if used > head_width {
+            break;
+        }
+        head.push_str(grapheme);
+    }
+    let mut tail = Vec::new();
+    let mut used = 0;
+    for grapheme in s.graphemes(true).rev() {
+        used += grapheme.width();
+        if used > tail_width {
+            break;
+        }
+        tail.push(grapheme);
+    }
+    tail.reverse();
+    format!("{head}{ELLIPSIS}{}", tail.concat())
+}
+
+/// Format a clickable hyperlink using the OSC 8 escape sequence.
+///
+/// On a TTY the text is emitted as a terminal hyperlink to `url`; otherwise it
+/// falls back to `text (url)` so the link is still visible.
+#[must_use]
+pub fn hyperlink(text: &str, url: &str) -> String {
+    format_hyperlink(text, url, is_tty() && !plain_mode())
+}
+
+/// Format a hyperlink as an OSC 8 sequence or as plain `text (url)`.
+fn format_hyperlink(text: &str, url: &str, terminal: bool) -> String {
+    if terminal {
+        format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+    } else {
+        format!("{text} ({url})")
+    }
+}
+
+/// Write a success message line to `w`.
+///
+/// Unlike [`success`], this formats directly into the writer without allocating,
+/// and colorization is decided by the caller.
+///
+/// # Errors
+/// Returns any error from writing to `w`.
+pub fn write_success<W: Write>(w: &mut W, msg: &str, colorize: bool) -> io::Result<()> {
+    writeln!(w, "{}", Message::new(Level::Success, msg, colorize))
+}
+
+/// Write an error message line to `w`.
+///
+/// # Errors
+/// Returns any error from writing to `w`.
+pub fn write_error<W: Write>(w: &mut W, msg: &str, colorize: bool) -> io::Result<()> {
+    writeln!(w, "{}", Message::new(Level::Error, msg, colorize))
+}
+
+/// Write a warning message line to `w`.
+///
+/// # Errors
+/// Returns any error from writing to `w`.
+pub fn write_warning<W: Write>(w: &mut W, msg: &str, colorize: bool) -> io::Result<()> {
+    writeln!(w, "{}", Message::new(Level::Warning, msg, colorize))
+}
+
+/// Write an info message line to `w`.
+///
+/// # Errors
+/// Returns any error from writing to `w`.
+pub fn write_info<W: Write>(w: &mut W, msg: &str, colorize: bool) -> io::Result<()> {
+    writeln!(w, "{}", Message::new(Level::Info, msg, colorize))
+}
+
+/// Write a header and its separator line to `w`.
+///
+/// # Errors
+/// Returns any error from writing to `w`.
+pub fn write_header<W: Write>(
+    w: &mut W,
+    title: &str,
+    width: usize,
+    colorize: bool,
+) -> io::Result<()> {
+    writeln!(
+        w,
+        "{}",
+        Header {
+            title,
+            width,
+            colorize,
+        }
+    )
+}
+
+/// Format a count with the singular or plural noun, e.g. "1 issue" or "3 issues".
+#[must_use]
+pub fn pluralize(n: usize, singular: &str, plural: &str) -> String {
+    format!("{n} {}", if n == 1 { singular } else { plural })
+}
+
+/// Format a health check tally like "12 passed, 2 failed, 1 warning".
+///
+/// Returns colored output when [`should_colorize`] allows it, plain text otherwise.
+#[must_use]
+pub fn check_counts(passed: usize, failed: usize, warnings: usize) -> String {
+    let colorize = should_colorize();
+    let theme = theme();
+    let passed = format!("{passed} passed");
+    let failed = format!("{failed} failed");
+    let warnings = pluralize(warnings, "warning", "warnings");
+    format!(
+        "{}, {}, {}",
+        Painted::new(&passed, theme.success, colorize),
+        Painted::new(&failed, theme.error, colorize),
+        Painted::new(&warnings, theme.warning, colorize)
+    )
+}
+
+/// Format a version upgrade like "1.2.0 → 1.3.0".
+///
+/// The current version is dimmed and the latest is highlighted in the theme's
+/// success color when [`should_colorize`] allows it. Returns "up to date" when
+/// the versions are equal, ignoring a leading `v`.
+#[must_use]
+pub fn format_version_upgrade(current: &str, latest: &str) -> String {
+    version_upgrade(current, latest, should_colorize())
+}
+
+fn version_upgrade(current: &str, latest: &str, colorize: bool) -> String {
+    let same = crate::version::compare_versions(current, latest).map_or_else(
+        || current.trim_start_matches('v') == latest.trim_start_matches('v'),
+        std::cmp::Ordering::is_eq,
+    );
+    if same {
+        return "up to date".to_string();
+    }
+    format!(
+        "{} {} {}",
+        Painted::new(current, Color::BrightBlack, colorize),
+        glyph("→", "->"),
+        Painted::new(latest, theme().success, colorize).bold()
+    )
+}
+
+/// Format a single `key: value` status line.
+///
+/// The key is bold and in the theme's header color when [`should_colorize`]
+/// allows it. Use [`kv_list`] to align several lines.
+#[must_use]
+pub fn kv(key: &str, value: &str) -> String {
+    format_kv_list(&[(key, value)], should_colorize())
+}
+
+/// Format a group of `key: value` lines with the values aligned.
+///
+/// Each key is followed by its colon and padded so every value starts in the
+/// same column, e.g. for version or doctor preambles. Multi-line values are not
+/// re-indented; use [`table`] for those.
+#[must_use]
+pub fn kv_list(pairs: &[(&str, &str)]) -> String {
+    format_kv_list(pairs, should_colorize())
+}
+
+fn format_kv_list(pairs: &[(&str, &str)], colorize: bool) -> String {
+    let key_width = pairs.iter().map(|(key, _)| key.width()).max().unwrap_or(0);
+    let mut out = String::new();
+    for (key, value) in pairs {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let padding = " ".repeat(key_width - key.width());
+        let key = format!("{key}:");
+        let _ = write!(
+            out,
+            "{} {padding}{value}",
+            Painted::new(&key, theme().header, colorize).bold()
+        );
+    }
+    out
+}
+
+/// Format key/value rows as aligned `key : value` lines.
+///
+/// Keys are padded to the longest key so the colons line up, and continuation
+/// lines of multi-line values are indented under the value column. Keys are
+/// colored when [`should_colorize`] allows it.
+#[must_use]
+pub fn table(rows: &[(&str, &str)]) -> String {
+    let colorize = should_colorize();
+    let key_color = theme().header;
+    let key_width = rows.iter().map(|(key, _)| key.width()).max().unwrap_or(0);
+    let indent = " ".repeat(key_width + 3);
+
+    let mut out = String::new();
+    for (key, value) in rows {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let padded = format!("{key}{}", " ".repeat(key_width - key.width()));
+        let _ = write!(
+            out,
+            "{} : ",
+            Painted::new(&padded, key_color, colorize).bold()
+        );
+        for (i, line) in value.lines().enumerate() {
+            if i > 0 {
+                out.push('\n');
+                out.push_str(&indent);
+            }
+            out.push_str(line);
+        }
+    }
+    out
+}
+
+/// Format a titled block: a themed title followed by `body` indented two spaces.
+///
+/// Every line of `body` is indented, and blank lines are kept (without
+/// trailing spaces). The title is bold in the theme's header color when
+/// [`should_colorize`] allows it.
+///
+/// # Examples
+/// ```
+/// use workhelix_cli_common::output;
+///
+/// let block = output::section("Paths", "config: ~/.config/mytool\ncache: ~/.cache/mytool");
+/// assert!(block.ends_with("\n  config: ~/.config/mytool\n  cache: ~/.cache/mytool"));
+/// ```
+#[must_use]
+pub fn section(title: &str, body: &str) -> String {
+    format!(
+        "{}\n{}",
+        Painted::new(title, theme().header, should_colorize()).bold(),
+        indent(body, 2)
+    )
+}
+
+/// Indent every non-blank line of `s` by `spaces` spaces.
+///
+/// Blank lines are left empty and the line structure, including any trailing
+/// newline, is preserved.
+#[must_use]
+pub fn indent(s: &str, spaces: usize) -> String {
+    let prefix = " ".repeat(spaces);
+    s.split('\n')
+        .map(|line| {
+            if line.trim().is_empty() {
+                String::new()
+            } else {
+                format!("{prefix}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Format items as a bulleted list, one item per line.
+///
+/// Each item is prefixed with a themed `•` bullet. Items longer than the
+/// terminal width are word-wrapped, with continuation lines indented to align
+/// under the item text.
+#[must_use]
+pub fn bullet_list(items: &[&str]) -> String {
+    format_bullet_list(items, terminal_width(), should_colorize())
+}
+
+/// Format a bulleted list wrapped to `width` columns.
+fn format_bullet_list(items: &[&str], width: usize, colorize: bool) -> String {
+    const INDENT: &str = "  ";
+    let bullet = Painted::new(glyph("•", "*"), theme().info, colorize);
+    let available = width.saturating_sub(INDENT.len()).max(1);
+
+    let mut out = String::new();
+    for item in items {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let _ = write!(out, "{bullet} ");
+        let mut line_width = 0;
+        for word in item.split_whitespace() {
+            let word_width = word.width();
+            if line_width > 0 && line_width + 1 + word_width > available {
+                out.push('\n');
+                out.push_str(INDENT);
+                line_width = 0;
+            } else if line_width > 0 {
+                out.push(' ');
+                line_width += 1;
+            }
+            out.push_str(word);
+            line_width += word_width;
+        }
+    }
+    out
+}
+
+/// Ask a yes/no question on stdout and read the answer from stdin.
+///
+/// The prompt is followed by `[Y/n]` or `[y/N]` depending on `default`.
+/// `y`/`yes` and `n`/`no` are accepted in any case, and an empty answer picks
+/// the default; anything else asks again. When stdin is not a TTY, or input
+/// ends, the default is returned without blocking.
+///
+/// # Errors
+/// Returns an error if reading stdin or writing stdout fails.
+///
+/// # Examples
+/// ```no_run
+/// use workhelix_cli_common::output;
+///
+/// if output::confirm("Install the update?", true)? {
+///     // ...
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn confirm(prompt: &str, default: bool) -> io::Result<bool> {
+    if !io::stdin().is_terminal() {
+        return Ok(default);
+    }
+    confirm_with(&mut io::stdin().lock(), &mut io::stdout(), prompt, default)
+}
+
+/// Ask a yes/no question on `out` and read the answer from `input`.
+///
+/// Behaves like [`confirm`] without the TTY check, so any reader can supply
+/// the answer (e.g., a byte slice in tests).
+///
+/// # Errors
+/// Returns an error if reading `input` or writing `out` fails.
+pub fn confirm_with<R: BufRead, W: Write>(
+    input: &mut R,
+    out: &mut W,
+    prompt: &str,
+    default: bool,
+) -> io::Result<bool> {
+    let choices = if default { "[Y/n]" } else { "[y/N]" };
+    loop {
+        write!(out, "{prompt} {choices} ")?;
+        out.flush()?;
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            writeln!(out)?;
+            return Ok(default);
+        }
+        match answer.trim().to_ascii_lowercase().as_str() {
+            "" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => writeln!(out, "Please answer y or n.")?,
+        }
+    }
+}
+
+/// Ask the user to pick one of `options` from a numbered list.
+///
+/// The options are printed numbered from 1 and the choice is read from stdin,
+/// asking again until a number in range is entered. Returns the 0-based index
+/// of the chosen option.
+///
+/// # Errors
+/// Returns an error if stdin is not a TTY (rather than waiting for input that
+/// will never come), if `options` is empty, if input ends before a valid
+/// choice, or if reading stdin or writing stdout fails.
+///
+/// # Examples
+/// ```no_run
+/// use workhelix_cli_common::output;
+///
+/// let shells = ["bash", "zsh", "fish"];
+/// let index = output::select("Install completions for which shell?", &shells)?;
+/// println!("Selected {}", shells[index]);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn select<T: fmt::Display>(prompt: &str, options: &[T]) -> io::Result<usize> {
+    if !io::stdin().is_terminal() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "cannot prompt for a selection: stdin is not a terminal",
+        ));
+    }
+    select_with(&mut io::stdin().lock(), &mut io::stdout(), prompt, options)
+}
+
+/// Ask the user to pick one of `options`, reading from `input` and writing to `out`.
+///
+/// Behaves like [`select`] without the TTY check.
+///
+/// # Errors
+/// Returns an error if `options` is empty, if input ends before a valid
+/// choice, or if reading `input` or writing `out` fails.
+pub fn select_with<R: BufRead, W: Write, T: fmt::Display>(
+    input: &mut R,
+    out: &mut W,
+    prompt: &str,
+    options: &[T],
+) -> io::Result<usize> {
+    if options.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no options to select from",
+        ));
+    }
+    writeln!(out, "{prompt}")?;
+    for (i, option) in options.iter().enumerate() {
+        writeln!(out, "  {}) {option}", i + 1)?;
+    }
+    loop {
+        write!(out, "Enter a number (1-{}): ", options.len())?;
+        out.flush()?;
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            writeln!(out)?;
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "input ended before a selection was made",
+            ));
+        }
+        match answer.trim().parse::<usize>() {
+            Ok(choice) if (1..=options.len()).contains(&choice) => return Ok(choice - 1),
+            _ => writeln!(out, "Invalid selection: {}", answer.trim())?,
+        }
+    }
+}
+
+/// A lightweight progress spinner for long-running operations.
+///
+/// On a terminal the spinner animates in place each time [`Spinner::tick`] is
+/// called, hiding the cursor while active. When output is not a terminal a single
+/// static line is printed instead. The line is cleared and the cursor restored
+/// on [`Spinner::finish`] or when the spinner is dropped.
+///
+/// # Examples
+/// ```no_run
+/// use workhelix_cli_common::output::Spinner;
+///
+/// let mut spinner = Spinner::start("Downloading release");
+/// for _ in 0..10 {
+///     // ... do a chunk of work ...
+///     spinner.tick();
+/// }
+/// spinner.finish("Download complete");
+/// ```
+pub struct Spinner<W: Write = io::Stderr> {
+    out: W,
+    message: String,
+    frames: &'static [&'static str],
+    frame: usize,
+    animate: bool,
+    active: bool,
+}
+
+/// Animation frames drawn by [`Spinner`].
+const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Animation frames drawn by [`Spinner`] when Unicode is not supported.
+const ASCII_SPINNER_FRAMES: &[&str] = &["|", "/", "-", "\\"];
+
+impl Spinner {
+    /// Start a spinner on stderr, animating only if stderr is a TTY and
+    /// [`plain_mode`] is off.
+    #[must_use]
+    pub fn start(msg: &str) -> Self {
+        Self::start_with(io::stderr(), msg, is_stderr_tty() && !plain_mode())
+    }
+}
+
+impl<W: Write> Spinner<W> {
+    /// Start a spinner writing to `out`.
+    ///
+    /// When `animate` is false, `msg` is written once as a plain line and
+    /// [`Spinner::tick`] does nothing.
+    pub fn start_with(mut out: W, msg: &str, animate: bool) -> Self {
+        let frames = if unicode_supported() {
+            SPINNER_FRAMES
+        } else {
+            ASCII_SPINNER_FRAMES
+        };
+        if animate {
+            let _ = write!(out, "\x1b[?25l\r{} {msg}", frames[0]);
+        } else {
+            let _ = writeln!(out, "{msg}...");
+        }
+        let _ = out.flush();
+        Self {
+            out,
+            message: msg.to_string(),
+            frames,
+            frame: 0,
+            animate,
+            active: true,
+        }
+    }
+
+    /// Advance the animation by one frame.
+    pub fn tick(&mut self) {
+        if !self.animate || !self.active {
+            return;
+        }
+        self.frame = (self.frame + 1) % self.frames.len();
+        let _ = write!(
+            self.out,
+            "\r\x1b[2K{} {}",
+            self.frames[self.frame], self.message
+        );
+        let _ = self.out.flush();
+    }
+
+    /// Stop the spinner and print a final message on its own line.
+    pub fn finish(mut self, msg: &str) {
+        self.clear();
+        let _ = writeln!(self.out, "{msg}");
+        let _ = self.out.flush();
+    }
+
+    /// Clear the spinner line and restore the cursor.
+    fn clear(&mut self) {
+        if self.active && self.animate {
+            let _ = write!(self.out, "\r\x1b[2K\x1b[?25h");
+        }
+        self.active = false;
+    }
+}
+
+impl<W: Write> Drop for Spinner<W> {
+    fn drop(&mut self) {
+        self.clear();
+        let _ = self.out.flush();
+    }
+}
+
+/// Format a byte count in decimal (SI) units, e.g. `1.5 MB` or `900 KB`.
+///
+/// Values under 100 keep one decimal place; larger ones are whole numbers.
+/// The unit is chosen after rounding, so a value that rounds up to 1000 moves
+/// to the next unit (`999_999` bytes is `1.0 MB`, not `1000 KB`).
+#[must_use]
+#[allow(clippy::cast_precision_loss)] // display only
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["KB", "MB", "GB", "TB", "PB", "EB"];
+    if bytes < 1000 {
+        return format!("{bytes} B");
+    }
+    let round = |value: f64| {
+        if value < 100.0 {
+            (value * 10.0).round() / 10.0
+        } else {
+            value.round()
+        }
+    };
+    let mut value = bytes as f64 / 1000.0;
+    let mut unit = 0;
+    while round(value) >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    let rounded = round(value);
+    if rounded < 100.0 {
+        format!("{rounded:.1} {}", UNITS[unit])
+    } else {
+        format!("{rounded:.0} {}", UNITS[unit])
+    }
+}
+
+/// Format a duration for people, e.g. `340ms`, `1.2s`, `2m 5s` or `1h 3m`.
+///
+/// Durations under a second are shown in whole milliseconds and under a minute
+/// in tenths of a second; longer ones drop the smaller units.
+#[must_use]
+pub fn format_duration(d: Duration) -> String {
+    let millis = d.as_millis();
+    let secs = d.as_secs();
+    if millis < 1000 {
+        format!("{millis}ms")
+    } else if secs < 60 {
+        format!("{secs}.{}s", d.subsec_millis() / 100)
+    } else if secs < 3600 {
+        format!("{}m {}s", secs / 60, secs % 60)
     } else {
-        format!("{title}\n{}", "=".repeat(width))
+        format!("{}h {}m", secs / 3600, secs % 3600 / 60)
+    }
+}
+
+/// A progress bar for operations of known size, such as downloads.
+///
+/// On a terminal the bar is redrawn in place as `[#####-----] 50% 2.1 MB/4.2 MB`,
+/// at most every 100ms. When output is not a terminal a plain line is printed
+/// each time another 10% is completed. Progress is clamped to the total.
+///
+/// # Examples
+/// ```no_run
+/// use workhelix_cli_common::output::ProgressBar;
+///
+/// let chunks: Vec<Vec<u8>> = Vec::new();
+/// let mut bar = ProgressBar::new(4_200_000);
+/// for chunk in &chunks {
+///     bar.inc(chunk.len() as u64);
+/// }
+/// bar.finish();
+/// ```
+pub struct ProgressBar<W: Write = io::Stderr> {
+    out: W,
+    total: u64,
+    position: u64,
+    animate: bool,
+    last_draw: Option<Instant>,
+    last_percent: u64,
+    active: bool,
+}
+
+/// Width of the `#`/`-` part of a [`ProgressBar`], in columns.
+const PROGRESS_BAR_WIDTH: u64 = 30;
+
+/// Minimum time between two redraws of an animated [`ProgressBar`].
+const PROGRESS_REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Percentage step between the lines printed by a non-animated [`ProgressBar`].
+const PROGRESS_LINE_STEP: u64 = 10;
+
+impl ProgressBar {
+    /// Create a progress bar on stderr, animating only if stderr is a TTY and
+    /// [`plain_mode`] is off.
+    #[must_use]
+    pub fn new(total: u64) -> Self {
+        Self::with_writer(io::stderr(), total, is_stderr_tty() && !plain_mode())
+    }
+}
+
+impl<W: Write> ProgressBar<W> {
+    /// Create a progress bar writing to `out`.
+    ///
+    /// When `animate` is false, plain percentage lines are written instead of
+    /// an in-place bar.
+    pub const fn with_writer(out: W, total: u64, animate: bool) -> Self {
+        Self {
+            out,
+            total,
+            position: 0,
+            animate,
+            last_draw: None,
+            last_percent: 0,
+            active: true,
+        }
+    }
+
+    /// Advance the progress by `n`, clamped to the total.
+    pub fn inc(&mut self, n: u64) {
+        if !self.active {
+            return;
+        }
+        self.position = self.position.saturating_add(n).min(self.total);
+        let percent = self.percent();
+        if self.animate {
+            let due = self
+                .last_draw
+                .is_none_or(|last| last.elapsed() >= PROGRESS_REDRAW_INTERVAL);
+            if due || percent == 100 {
+                self.draw_bar();
+            }
+        } else if percent / PROGRESS_LINE_STEP > self.last_percent / PROGRESS_LINE_STEP {
+            let _ = writeln!(self.out, "{}", self.status());
+            self.last_percent = percent;
+        }
+    }
+
+    /// Progress so far, never more than the total.
+    #[must_use]
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Draw the final state and end the progress line.
+    pub fn finish(mut self) {
+        if self.animate {
+            self.draw_bar();
+            let _ = writeln!(self.out);
+        } else if self.percent() != self.last_percent {
+            let _ = writeln!(self.out, "{}", self.status());
+        }
+        let _ = self.out.flush();
+        self.active = false;
+    }
+
+    fn percent(&self) -> u64 {
+        if self.total == 0 {
+            100
+        } else {
+            u64::try_from(u128::from(self.position) * 100 / u128::from(self.total)).unwrap_or(100)
+        }
+    }
+
+    /// `50% 2.1 MB/4.2 MB`
+    fn status(&self) -> String {
+        format!(
+            "{}% {}/{}",
+            self.percent(),
+            format_bytes(self.position),
+            format_bytes(self.total)
+        )
+    }
+
+    fn draw_bar(&mut self) {
+        let filled = self.percent() * PROGRESS_BAR_WIDTH / 100;
+        let bar = format!(
+            "{}{}",
+            "#".repeat(usize::try_from(filled).unwrap_or(0)),
+            "-".repeat(usize::try_from(PROGRESS_BAR_WIDTH - filled).unwrap_or(0))
+        );
+        let _ = write!(self.out, "\r\x1b[2K[{bar}] {}", self.status());
+        let _ = self.out.flush();
+        self.last_draw = Some(Instant::now());
+    }
+}
+
+impl<W: Write> Drop for ProgressBar<W> {
+    fn drop(&mut self) {
+        // End an interrupted in-place bar so later output starts on a fresh line
+        if self.active && self.animate && self.last_draw.is_some() {
+            let _ = writeln!(self.out);
+            let _ = self.out.flush();
+        }
+    }
+}
+
+/// Message severity, determining icon, plain-text label and theme color.
+#[derive(Debug, Clone, Copy)]
+enum Level {
+    Success,
+    Error,
+    Warning,
+    Info,
+}
+
+impl Level {
+    const fn icon(self) -> &'static str {
+        match self {
+            Self::Success => "✅",
+            Self::Error => "❌",
+            Self::Warning => "⚠️",
+            Self::Info => "ℹ️",
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Success => "OK",
+            Self::Error => "ERROR",
+            Self::Warning => "WARNING",
+            Self::Info => "INFO",
+        }
+    }
+
+    /// Errors are emphasized in bold so they stand out.
+    const fn is_bold(self) -> bool {
+        matches!(self, Self::Error)
+    }
+
+    const fn color(self, theme: &Theme) -> Color {
+        match self {
+            Self::Success => theme.success,
+            Self::Error => theme.error,
+            Self::Warning => theme.warning,
+            Self::Info => theme.info,
+        }
+    }
+}
+
+/// A status message rendered either as icon plus colored text or as `[LABEL] text`.
+struct Message<'a> {
+    level: Level,
+    msg: &'a str,
+    colorize: bool,
+}
+
+impl<'a> Message<'a> {
+    const fn new(level: Level, msg: &'a str, colorize: bool) -> Self {
+        Self {
+            level,
+            msg,
+            colorize,
+        }
+    }
+}
+
+impl fmt::Display for Message<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.colorize {
+            let color = self.level.color(&theme());
+            let mut text = Painted::new(self.msg, color, true);
+            text.bold = self.level.is_bold();
+            if emoji_enabled() {
+                write!(f, "{} {text}", Painted::new(self.level.icon(), color, true))
+            } else {
+                let marker = format!("[{}]", self.level.label());
+                write!(f, "{} {text}", Painted::new(&marker, color, true))
+            }
+        } else {
+            write!(f, "[{}] {}", self.level.label(), self.msg)
+        }
+    }
+}
+
+/// A bold title followed by a `=` separator line.
+struct Header<'a> {
+    title: &'a str,
+    width: usize,
+    colorize: bool,
+}
+
+impl fmt::Display for Header<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = wrap_words(self.title, self.width);
+        let widest = lines.iter().map(|line| line.width()).max().unwrap_or(0);
+        let separator = "=".repeat(self.width.max(widest));
+        let color = theme().header;
+        for line in &lines {
+            writeln!(f, "{}", Painted::new(line, color, self.colorize).bold())?;
+        }
+        write!(f, "{}", Painted::new(&separator, color, self.colorize))
+    }
+}
+
+/// Greedily wrap `text` at whitespace into lines of at most `width` columns.
+///
+/// Words wider than `width` get a line of their own rather than being split.
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    if text.width() <= width {
+        return vec![text.to_string()];
+    }
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.width() + 1 + word.width() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+    lines
+}
+
+/// Text wrapped in ANSI color codes when colorization is enabled.
+///
+/// The escape codes are emitted directly rather than through `colored`'s
+/// global detection, so the caller's colorize decision is always honored.
+struct Painted<'a> {
+    text: &'a str,
+    color: Color,
+    bold: bool,
+    enabled: bool,
+}
+
+impl<'a> Painted<'a> {
+    const fn new(text: &'a str, color: Color, enabled: bool) -> Self {
+        Self {
+            text,
+            color,
+            bold: false,
+            enabled,
+        }
+    }
+
+    const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+}
+
+impl fmt::Display for Painted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.enabled {
+            return f.write_str(self.text);
+        }
+        let bold = if self.bold { "1;" } else { "" };
+        write!(
+            f,
+            "\x1b[{bold}{}m{}\x1b[0m",
+            self.color.to_fg_str(),
+            self.text
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Mutex;
+
+    /// Serializes tests that assert exact colors against tests that change the theme.
+    static THEME_LOCK: Mutex<()> = Mutex::new(());
 
     #[test]
     fn test_is_tty_returns_bool() {
@@ -114,10 +1364,679 @@ mod tests {
         assert!(msg.contains("ℹ️") || msg.contains("[INFO]"));
     }
 
+    #[test]
+    fn test_pluralize() {
+        assert_eq!(pluralize(0, "issue", "issues"), "0 issues");
+        assert_eq!(pluralize(1, "issue", "issues"), "1 issue");
+        assert_eq!(pluralize(2, "issue", "issues"), "2 issues");
+        assert_eq!(pluralize(2, "entry", "entries"), "2 entries");
+    }
+
+    #[test]
+    fn test_check_counts_format() {
+        let msg = check_counts(12, 2, 1);
+        assert!(msg.contains("12 passed"));
+        assert!(msg.contains("2 failed"));
+        assert!(msg.contains("1 warning"));
+        assert!(!msg.contains("1 warnings"));
+        assert!(check_counts(1, 0, 3).contains("3 warnings"));
+    }
+
+    #[test]
+    fn test_format_version_upgrade_plain() {
+        crate::test_env::with_var("NO_COLOR", Some("1"), || {
+            assert_eq!(format_version_upgrade("1.2.0", "1.3.0"), "1.2.0 → 1.3.0");
+            assert_eq!(format_version_upgrade("1.2.0", "1.2.0"), "up to date");
+            assert_eq!(format_version_upgrade("v1.2.0", "1.2.0"), "up to date");
+        });
+    }
+
+    #[test]
+    fn test_version_upgrade_colored() {
+        let _guard = THEME_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        assert_eq!(
+            version_upgrade("1.2.0", "1.3.0", true),
+            "\x1b[90m1.2.0\x1b[0m → \x1b[1;32m1.3.0\x1b[0m"
+        );
+    }
+
+    fn ask(input: &str, default: bool) -> (bool, String) {
+        let mut out = Vec::new();
+        let answer = confirm_with(&mut input.as_bytes(), &mut out, "Continue?", default).unwrap();
+        (answer, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_confirm_with_answers() {
+        assert!(ask("y\n", false).0);
+        assert!(ask("YES\n", false).0);
+        assert!(!ask("n\n", true).0);
+        assert!(!ask(" No \n", true).0);
+    }
+
+    #[test]
+    fn test_confirm_with_default() {
+        let (answer, out) = ask("\n", true);
+        assert!(answer);
+        assert_eq!(out, "Continue? [Y/n] ");
+
+        let (answer, out) = ask("\n", false);
+        assert!(!answer);
+        assert_eq!(out, "Continue? [y/N] ");
+
+        // End of input falls back to the default
+        assert!(ask("", true).0);
+    }
+
+    #[test]
+    fn test_confirm_with_reprompts_on_invalid_answer() {
+        let (answer, out) = ask("maybe\ny\n", false);
+        assert!(answer);
+        assert_eq!(out.matches("Continue? [y/N] ").count(), 2);
+        assert!(out.contains("Please answer y or n."));
+    }
+
+    #[test]
+    fn test_select_with_valid_pick() {
+        let mut out = Vec::new();
+        let index = select_with(&mut &b"2\n"[..], &mut out, "Shell?", &["bash", "zsh"]).unwrap();
+        assert_eq!(index, 1);
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out, "Shell?\n  1) bash\n  2) zsh\nEnter a number (1-2): ");
+    }
+
+    #[test]
+    fn test_select_with_reprompts_out_of_range() {
+        let mut out = Vec::new();
+        let index = select_with(
+            &mut &b"3\nzsh\n1\n"[..],
+            &mut out,
+            "Shell?",
+            &["bash", "zsh"],
+        )
+        .unwrap();
+        assert_eq!(index, 0);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Invalid selection: 3"));
+        assert!(out.contains("Invalid selection: zsh"));
+        assert_eq!(out.matches("Enter a number").count(), 3);
+    }
+
+    #[test]
+    fn test_select_with_errors() {
+        let err = select_with(&mut &b""[..], &mut Vec::new(), "Shell?", &["bash"]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let none: [&str; 0] = [];
+        let err = select_with(&mut &b"1\n"[..], &mut Vec::new(), "Shell?", &none).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_indent() {
+        assert_eq!(indent("a\nb", 2), "  a\n  b");
+        assert_eq!(indent("a\n\n  b\n", 3), "   a\n\n     b\n");
+        assert_eq!(indent("", 4), "");
+    }
+
+    #[test]
+    fn test_section_indents_multiline_body() {
+        crate::test_env::with_var("NO_COLOR", Some("1"), || {
+            assert_eq!(
+                section("Network:", "first\nsecond\n\nthird"),
+                "Network:\n  first\n  second\n\n  third"
+            );
+        });
+    }
+
+    #[test]
+    fn test_locale_is_unicode() {
+        assert!(locale_is_unicode(None));
+        assert!(locale_is_unicode(Some("C")));
+        assert!(locale_is_unicode(Some("en_US.UTF-8")));
+        assert!(locale_is_unicode(Some("de_DE.utf8@euro")));
+        assert!(!locale_is_unicode(Some("en_US.ISO-8859-1")));
+        assert!(!locale_is_unicode(Some("ja_JP.eucJP")));
+    }
+
+    #[test]
+    fn test_ascii_env_disables_unicode() {
+        let _guard = THEME_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let vars = [(ASCII_ENV, Some("1")), (NO_EMOJI_ENV, None)];
+        crate::test_env::with_vars(&vars, || {
+            assert!(!unicode_supported());
+            assert!(!emoji_enabled());
+            assert_eq!(
+                Message::new(Level::Success, "saved", true).to_string(),
+                "\x1b[32m[OK]\x1b[0m \x1b[32msaved\x1b[0m"
+            );
+            assert_eq!(format_bullet_list(&["item"], 80, false), "* item");
+            assert_eq!(version_upgrade("1.0.0", "1.1.0", false), "1.0.0 -> 1.1.0");
+
+            let mut out = Vec::new();
+            let mut spinner = Spinner::start_with(&mut out, "Working", true);
+            spinner.tick();
+            spinner.finish("Done");
+            let text = String::from_utf8(out).unwrap();
+            assert!(text.contains("| Working") && text.contains("/ Working"));
+            assert!(text.is_ascii());
+        });
+    }
+
+    /// The classic Windows console can't render emoji; `WH_ASCII` forces the
+    /// fallback even in a terminal that would otherwise be trusted.
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_ascii_fallback() {
+        let vars = [
+            ("WT_SESSION", Some("1")),
+            (ASCII_ENV, None),
+            (NO_EMOJI_ENV, None),
+        ];
+        crate::test_env::with_vars(&vars, || assert!(unicode_supported()));
+        let vars = [
+            ("WT_SESSION", Some("1")),
+            (ASCII_ENV, Some("1")),
+            (NO_EMOJI_ENV, None),
+        ];
+        crate::test_env::with_vars(&vars, || {
+            assert!(!unicode_supported());
+            assert!(!emoji_enabled());
+        });
+        let vars = [
+            ("WT_SESSION", None),
+            ("ConEmuANSI", None),
+            ("TERM_PROGRAM", None),
+            ("TERM", None),
+            (ASCII_ENV, None),
+        ];
+        crate::test_env::with_vars(&vars, || assert!(!unicode_supported()));
+    }
+
+    #[test]
+    fn test_emoji_toggle() {
+        let _guard = THEME_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let render = || Message::new(Level::Success, "saved", true).to_string();
+        crate::test_env::with_var(NO_EMOJI_ENV, None, || {
+            assert!(render().contains('✅'));
+
+            set_emoji_enabled(false);
+            let out = render();
+            set_emoji_enabled(true);
+            assert_eq!(out, "\x1b[32m[OK]\x1b[0m \x1b[32msaved\x1b[0m");
+            assert!(
+                Message::new(Level::Error, "failed", true)
+                    .to_string()
+                    .contains('❌')
+            );
+        });
+        crate::test_env::with_var(NO_EMOJI_ENV, Some("1"), || {
+            assert!(render().starts_with("\x1b[32m[OK]"));
+        });
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1500), "1.5 KB");
+        assert_eq!(format_bytes(900_000), "900 KB");
+        assert_eq!(format_bytes(1_500_000), "1.5 MB");
+        assert_eq!(format_bytes(5_000_000_000), "5.0 GB");
+    }
+
+    #[test]
+    fn test_format_bytes_thresholds() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(999), "999 B");
+        assert_eq!(format_bytes(1000), "1.0 KB");
+        assert_eq!(format_bytes(99_949), "99.9 KB");
+        assert_eq!(format_bytes(99_950), "100 KB");
+        assert_eq!(format_bytes(999_499), "999 KB");
+        assert_eq!(format_bytes(999_500), "1.0 MB");
+        assert_eq!(format_bytes(1_048_575), "1.0 MB");
+        assert_eq!(format_bytes(999_999_999), "1.0 GB");
+        assert_eq!(format_bytes(1_000_000_000), "1.0 GB");
+        assert_eq!(format_bytes(u64::MAX), "18.4 EB");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::ZERO), "0ms");
+        assert_eq!(format_duration(Duration::from_micros(340_900)), "340ms");
+        assert_eq!(format_duration(Duration::from_millis(999)), "999ms");
+        assert_eq!(format_duration(Duration::from_secs(1)), "1.0s");
+        assert_eq!(format_duration(Duration::from_millis(1250)), "1.2s");
+        assert_eq!(format_duration(Duration::from_millis(59_999)), "59.9s");
+        assert_eq!(format_duration(Duration::from_secs(60)), "1m 0s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m 5s");
+        assert_eq!(
+            format_duration(Duration::from_secs(59 * 60 + 59)),
+            "59m 59s"
+        );
+        assert_eq!(format_duration(Duration::from_secs(3780)), "1h 3m");
+    }
+
+    #[test]
+    fn test_progress_bar_plain_lines_and_clamp() {
+        let mut out = Vec::new();
+        let mut bar = ProgressBar::with_writer(&mut out, 2048, false);
+        bar.inc(512);
+        bar.inc(100);
+        bar.inc(512);
+        bar.inc(4096);
+        assert_eq!(bar.position(), 2048);
+        bar.finish();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(
+            text,
+            "25% 512 B/2.0 KB\n54% 1.1 KB/2.0 KB\n100% 2.0 KB/2.0 KB\n"
+        );
+        assert!(!text.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_progress_bar_animated() {
+        let mut out = Vec::new();
+        let mut bar = ProgressBar::with_writer(&mut out, 10, true);
+        bar.inc(5);
+        // Throttled: the next redraw is not due yet
+        bar.inc(1);
+        bar.inc(100);
+        bar.finish();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("\r\x1b[2K[###############---------------] 50% 5 B/10 B"));
+        assert!(!text.contains("60%"));
+        assert!(text.ends_with("[##############################] 100% 10 B/10 B\n"));
+    }
+
+    #[test]
+    fn test_is_stderr_tty_returns_bool() {
+        let _result = is_stderr_tty();
+    }
+
+    #[test]
+    fn test_stderr_formatting_plain_when_redirected() {
+        let plain = |level, msg| Message::new(level, msg, false).to_string();
+        assert_eq!(plain(Level::Error, "bad"), "[ERROR] bad");
+        assert_eq!(plain(Level::Warning, "careful"), "[WARNING] careful");
+
+        let vars = [("NO_COLOR", None), ("CLICOLOR_FORCE", None)];
+        crate::test_env::with_vars(&vars, || {
+            if !is_stderr_tty() {
+                assert!(!should_colorize_stderr());
+            }
+            eprint_error("test error");
+            eprint_warning("test warning");
+        });
+    }
+
+    #[test]
+    fn test_colorize_decision() {
+        assert!(!colorize_decision(true, false, true));
+        assert!(!colorize_decision(true, true, true));
+        assert!(colorize_decision(false, true, false));
+        assert!(colorize_decision(false, false, true));
+        assert!(!colorize_decision(false, false, false));
+    }
+
+    #[test]
+    fn test_color_choice_always() {
+        let out = Message::new(Level::Success, "saved", ColorChoice::Always.resolve(false));
+        assert!(out.to_string().contains('\x1b'));
+        let vars = [("NO_COLOR", Some("1")), ("CLICOLOR_FORCE", None)];
+        crate::test_env::with_vars(&vars, || {
+            assert!(ColorChoice::Always.resolve(false));
+        });
+    }
+
+    #[test]
+    fn test_color_choice_never() {
+        let out = Message::new(Level::Success, "saved", ColorChoice::Never.resolve(true));
+        assert!(!out.to_string().contains('\x1b'));
+        let vars = [("NO_COLOR", None), ("CLICOLOR_FORCE", Some("1"))];
+        crate::test_env::with_vars(&vars, || {
+            assert!(!ColorChoice::Never.resolve(true));
+
+            // Overrides CLICOLOR_FORCE for every formatter
+            let _guard = THEME_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+            assert!(success("saved").contains('\x1b'));
+            set_color_choice(ColorChoice::Never);
+            let out = success("saved");
+            set_color_choice(ColorChoice::Auto);
+            assert!(!out.contains('\x1b'));
+            assert_eq!(color_choice(), ColorChoice::Auto);
+        });
+    }
+
+    #[test]
+    fn test_plain_mode_overrides_forced_color() {
+        let vars = [
+            ("NO_COLOR", None),
+            ("CLICOLOR_FORCE", Some("1")),
+            (ASCII_ENV, None),
+            ("LC_ALL", Some("C.UTF-8")),
+            ("WT_SESSION", Some("1")),
+        ];
+        crate::test_env::with_vars(&vars, || {
+            let _guard = THEME_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+            set_color_choice(ColorChoice::Always);
+            set_plain_mode(true);
+            let out = (success("saved"), unicode_supported(), emoji_enabled());
+            set_plain_mode(false);
+            let decorated = success("saved");
+            set_color_choice(ColorChoice::Auto);
+
+            assert_eq!(out, ("[OK] saved".to_string(), false, false));
+            assert!(decorated.contains('\x1b'));
+            assert!(!plain_mode());
+        });
+    }
+
+    #[test]
+    fn test_color_choice_auto() {
+        let vars = [("NO_COLOR", None), ("CLICOLOR_FORCE", None)];
+        crate::test_env::with_vars(&vars, || {
+            let tty = Message::new(Level::Success, "saved", ColorChoice::Auto.resolve(true));
+            assert!(tty.to_string().contains('\x1b'));
+            let piped = Message::new(Level::Success, "saved", ColorChoice::Auto.resolve(false));
+            assert!(!piped.to_string().contains('\x1b'));
+        });
+        crate::test_env::with_var("NO_COLOR", Some("1"), || {
+            assert!(!ColorChoice::Auto.resolve(true));
+        });
+    }
+
+    #[test]
+    fn test_no_color_forces_plain() {
+        let vars = [("CLICOLOR_FORCE", Some("1")), ("NO_COLOR", Some("1"))];
+        crate::test_env::with_vars(&vars, || {
+            assert!(!should_colorize());
+            assert_eq!(success("done"), "[OK] done");
+            assert_eq!(error("bad"), "[ERROR] bad");
+        });
+    }
+
+    #[test]
+    fn test_clicolor_force_enables_color() {
+        let vars = [("NO_COLOR", None), ("CLICOLOR_FORCE", Some("1"))];
+        crate::test_env::with_vars(&vars, || {
+            assert!(should_colorize());
+            assert!(success("done").contains("✅"));
+            assert!(warning("careful").contains("⚠️"));
+            assert!(!info("fyi").contains("[INFO]"));
+        });
+    }
+
+    #[test]
+    fn test_write_functions_plain() {
+        let mut buf = Vec::new();
+        write_success(&mut buf, "saved", false).unwrap();
+        write_error(&mut buf, "failed", false).unwrap();
+        write_warning(&mut buf, "careful", false).unwrap();
+        write_info(&mut buf, "fyi", false).unwrap();
+        write_header(&mut buf, "Title", 5, false).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "[OK] saved\n[ERROR] failed\n[WARNING] careful\n[INFO] fyi\nTitle\n=====\n"
+        );
+    }
+
+    #[test]
+    fn test_write_functions_colored() {
+        let _guard = THEME_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut buf = Vec::new();
+        write_success(&mut buf, "saved", true).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert_eq!(out, "\x1b[32m✅\x1b[0m \x1b[32msaved\x1b[0m\n");
+
+        let mut buf = Vec::new();
+        write_error(&mut buf, "failed", true).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert!(out.contains("\x1b[1;31mfailed\x1b[0m"));
+
+        let mut buf = Vec::new();
+        write_header(&mut buf, "Title", 3, true).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert_eq!(out, "\x1b[1;36mTitle\x1b[0m\n\x1b[36m=====\x1b[0m\n");
+    }
+
+    #[test]
+    fn test_table_alignment() {
+        crate::test_env::with_var("NO_COLOR", Some("1"), || {
+            let out = table(&[
+                ("Name", "mytool"),
+                ("Version", "1.2.3"),
+                ("Install path", "/usr/local/bin\n/opt/bin"),
+            ]);
+            let lines: Vec<_> = out.lines().collect();
+            assert_eq!(lines[0], "Name         : mytool");
+            assert_eq!(lines[1], "Version      : 1.2.3");
+            assert_eq!(lines[2], "Install path : /usr/local/bin");
+            assert_eq!(lines[3], "               /opt/bin");
+
+            let colons: Vec<_> = lines[..3].iter().map(|l| l.find(" : ")).collect();
+            assert!(colons.iter().all(|c| *c == colons[0]));
+        });
+        assert_eq!(table(&[]), "");
+    }
+
+    #[test]
+    fn test_truncate_middle_short_unchanged() {
+        assert_eq!(truncate_middle("/etc/hosts", 10), "/etc/hosts");
+        assert_eq!(truncate_middle("/etc/hosts", 80), "/etc/hosts");
+        assert_eq!(truncate_middle("", 0), "");
+    }
+
+    #[test]
+    fn test_truncate_middle_long() {
+        let path = "/Users/alice/projects/workhelix/config.toml";
+        let out = truncate_middle(path, 20);
+        assert_eq!(out, "/Users/al…onfig.toml");
+        assert_eq!(out.width(), 20);
+        assert_eq!(truncate_middle(path, 1), "…");
+        assert_eq!(truncate_middle(path, 0), "");
+
+        // Multibyte characters are never split
+        let out = truncate_middle("/home/jürgen/ümlaut/dätei.toml", 12);
+        assert_eq!(out, "/home…i.toml");
+        let out = truncate_middle("ääääääääää", 5);
+        assert_eq!(out, "ää…ää");
+    }
+
+    #[test]
+    fn test_kv_list_alignment() {
+        let out = format_kv_list(
+            &[("OS", "Linux"), ("Version", "1.2.3"), ("Target", "x86_64")],
+            false,
+        );
+        assert_eq!(out, "OS:      Linux\nVersion: 1.2.3\nTarget:  x86_64");
+        let columns: Vec<_> = out.lines().map(|line| line.find(['L', '1', 'x'])).collect();
+        assert!(columns.iter().all(|c| *c == Some(9)));
+        assert_eq!(format_kv_list(&[], false), "");
+    }
+
+    #[test]
+    fn test_kv() {
+        assert_eq!(format_kv_list(&[("Status", "ok")], false), "Status: ok");
+        let _guard = THEME_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        assert_eq!(
+            format_kv_list(&[("Status", "ok")], true),
+            "\x1b[1;36mStatus:\x1b[0m ok"
+        );
+        crate::test_env::with_var("NO_COLOR", Some("1"), || {
+            assert_eq!(kv("Status", "ok"), "Status: ok");
+        });
+    }
+
+    #[test]
+    fn test_terminal_width_default() {
+        let width = terminal_width();
+        assert!(width > 0);
+        if !is_tty() {
+            assert_eq!(width, DEFAULT_TERMINAL_WIDTH);
+        }
+        crate::test_env::with_var("NO_COLOR", Some("1"), || {
+            let expected = format!("Title\n{}", "=".repeat(terminal_width()));
+            assert_eq!(header_default("Title"), expected);
+        });
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(truncate_to_width("short", 10), "short");
+        assert_eq!(truncate_to_width("exactly", 7), "exactly");
+        assert_eq!(truncate_to_width("truncate me", 6), "trunc…");
+    }
+
+    #[test]
+    fn test_truncate_to_width_multibyte() {
+        // Wide CJK characters take two columns each
+        assert_eq!(truncate_to_width("日本語テキスト", 7), "日本語…");
+        // Combining sequences stay intact
+        assert_eq!(
+            truncate_to_width("e\u{301}e\u{301}e\u{301}", 2),
+            "e\u{301}…"
+        );
+        // Emoji are not split
+        assert_eq!(truncate_to_width("👍👍👍", 4), "👍…");
+    }
+
+    #[test]
+    fn test_truncate_to_width_smaller_than_ellipsis() {
+        assert_eq!(truncate_to_width("hello", 1), "…");
+        assert_eq!(truncate_to_width("hello", 0), "");
+    }
+
+    #[test]
+    fn test_hyperlink() {
+        let plain = format_hyperlink("docs", "https://example.com", false);
+        assert_eq!(plain, "docs (https://example.com)");
+
+        let linked = format_hyperlink("docs", "https://example.com", true);
+        assert!(linked.starts_with("\x1b]8;;https://example.com"));
+        assert!(linked.contains("docs"));
+
+        let link = hyperlink("docs", "https://example.com");
+        assert!(link.contains("docs"));
+    }
+
+    #[test]
+    fn test_spinner_plain_when_not_tty() {
+        let mut buf = Vec::new();
+        let mut spinner = Spinner::start_with(&mut buf, "Downloading", false);
+        spinner.tick();
+        spinner.tick();
+        spinner.finish("Done");
+        assert_eq!(String::from_utf8(buf).unwrap(), "Downloading...\nDone\n");
+    }
+
+    #[test]
+    fn test_spinner_animates_and_restores_cursor() {
+        let mut buf = Vec::new();
+        let mut spinner = Spinner::start_with(&mut buf, "Working", true);
+        spinner.tick();
+        spinner.finish("Done");
+        let out = String::from_utf8(buf).unwrap();
+        assert!(out.starts_with("\x1b[?25l"));
+        assert!(out.contains("⠙ Working"));
+        assert!(out.ends_with("\r\x1b[2K\x1b[?25hDone\n"));
+
+        let mut buf = Vec::new();
+        drop(Spinner::start_with(&mut buf, "Working", true));
+        assert!(String::from_utf8(buf).unwrap().ends_with("\x1b[?25h"));
+    }
+
+    #[test]
+    fn test_set_theme_changes_colors() {
+        let _guard = THEME_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        assert_eq!(theme(), Theme::default());
+
+        set_theme(Theme {
+            success: Color::Magenta,
+            header: Color::BrightBlue,
+            ..Theme::default()
+        });
+        let mut buf = Vec::new();
+        write_success(&mut buf, "saved", true).unwrap();
+        write_header(&mut buf, "Title", 1, true).unwrap();
+        set_theme(Theme::default());
+
+        let out = String::from_utf8(buf).unwrap();
+        assert!(out.starts_with("\x1b[35m✅\x1b[0m \x1b[35msaved\x1b[0m\n"));
+        assert!(out.contains("\x1b[1;94mTitle"));
+        assert!(!out.contains("\x1b[32m"));
+    }
+
+    #[test]
+    fn test_bullet_list() {
+        let out = format_bullet_list(&["Commercial use", "Modification"], 80, false);
+        assert_eq!(out, "• Commercial use\n• Modification");
+
+        let out = format_bullet_list(
+            &[
+                "short",
+                "a much longer item that needs to wrap onto more lines",
+            ],
+            20,
+            false,
+        );
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines[0], "• short");
+        assert!(lines[1].starts_with("• "));
+        assert!(lines.len() > 2);
+        for line in &lines[2..] {
+            assert!(line.starts_with("  ") && !line.starts_with("   "));
+            assert!(line.width() <= 20);
+        }
+
+        assert!(bullet_list(&["item"]).contains("item"));
+        assert_eq!(bullet_list(&[]), "");
+    }
+
     #[test]
     fn test_header_format() {
         let msg = header("Test Header", 20);
         assert!(msg.contains("Test Header"));
         assert!(msg.contains("===================="));
     }
+
+    #[test]
+    fn test_header_wraps_long_title() {
+        let header = |title, width| {
+            Header {
+                title,
+                width,
+                colorize: false,
+            }
+            .to_string()
+        };
+        assert_eq!(
+            header("Diagnostics for the workhelix tool", 16),
+            "Diagnostics for\nthe workhelix\ntool\n================"
+        );
+        // An unbreakable word widens the separator to match
+        assert_eq!(
+            header("configuration-directory check", 10),
+            "configuration-directory\ncheck\n======================="
+        );
+        assert_eq!(header("Short", 8), "Short\n========");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_header_auto_uses_terminal_width() {
+        let msg = header_default("Status");
+        let separator = msg.lines().last().unwrap();
+        assert_eq!(separator.matches('=').count(), terminal_width());
+        assert_eq!(msg, header_auto("Status"));
+        assert_eq!(
+            Header {
+                title: "Status",
+                width: DEFAULT_TERMINAL_WIDTH,
+                colorize: false,
+            }
+            .to_string(),
+            format!("Status\n{}", "=".repeat(80))
+        );
+    }
 }
@@ -0,0 +1,158 @@
+//! Information about the platform the binary is running on.
+
+use std::path::PathBuf;
+
+/// Target triple of the running binary, as used in release asset names.
+pub fn target_triple() -> String {
+    let os = match std::env::consts::OS {
+        "linux" if cfg!(target_env = "musl") => "unknown-linux-musl",
+        "linux" => "unknown-linux-gnu",
+        "macos" => "apple-darwin",
+        "windows" => "pc-windows-msvc",
+        "freebsd" => "unknown-freebsd",
+        other => other,
+    };
+    format!("{}-{os}", std::env::consts::ARCH)
+}
+
+/// Human-readable operating system version, where it can be determined.
+///
+/// Linux reads `PRETTY_NAME` from `/etc/os-release`; macOS asks `sw_vers`.
+pub fn os_version() -> Option<String> {
+    match std::env::consts::OS {
+        "linux" => std::fs::read_to_string("/etc/os-release")
+            .ok()
+            .and_then(|contents| pretty_name(&contents)),
+        "macos" => std::process::Command::new("sw_vers")
+            .arg("-productVersion")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| format!("macOS {}", String::from_utf8_lossy(&output.stdout).trim())),
+        _ => None,
+    }
+}
+
+/// Path of a tool's config file in the platform's conventional location.
+///
+/// - Linux and other Unix: `$XDG_CONFIG_HOME/{tool}/{file}`, falling back to
+///   `~/.config/{tool}/{file}`
+/// - macOS: `~/Library/Application Support/{tool}/{file}`
+/// - Windows: `%APPDATA%\{tool}\{file}`
+///
+/// If the base directory can't be determined, the path is relative:
+/// `{tool}/{file}`.
+///
+/// # Examples
+/// ```
+/// use workhelix_cli_common::config_path;
+///
+/// let path = config_path("mytool", "config.toml");
+/// assert!(path.ends_with("mytool/config.toml"));
+/// ```
+#[must_use]
+pub fn config_path(tool: &str, file: &str) -> PathBuf {
+    config_dir().unwrap_or_default().join(tool).join(file)
+}
+
+/// Base directory for per-user configuration.
+#[cfg(target_os = "macos")]
+fn config_dir() -> Option<PathBuf> {
+    home::home_dir().map(|home| home.join("Library").join("Application Support"))
+}
+
+/// Base directory for per-user configuration.
+#[cfg(windows)]
+fn config_dir() -> Option<PathBuf> {
+    std::env::var_os("APPDATA")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home::home_dir().map(|home| home.join("AppData").join("Roaming")))
+}
+
+/// Base directory for per-user configuration.
+///
+/// Per the XDG spec, a relative `XDG_CONFIG_HOME` is ignored.
+#[cfg(not(any(target_os = "macos", windows)))]
+fn config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| home::home_dir().map(|home| home.join(".config")))
+}
+
+/// Extract `PRETTY_NAME` from the contents of an `os-release` file.
+fn pretty_name(os_release: &str) -> Option<String> {
+    os_release.lines().find_map(|line| {
+        let value = line.strip_prefix("PRETTY_NAME=")?;
+        let value = value.trim().trim_matches('"');
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_triple() {
+        assert!(target_triple().starts_with(std::env::consts::ARCH));
+    }
+
+    #[cfg(not(any(target_os = "macos", windows)))]
+    #[test]
+    fn test_config_path_xdg() {
+        let vars = [
+            ("XDG_CONFIG_HOME", Some("/xdg")),
+            ("HOME", Some("/home/user")),
+        ];
+        crate::test_env::with_vars(&vars, || {
+            assert_eq!(
+                config_path("mytool", "config.toml"),
+                PathBuf::from("/xdg/mytool/config.toml")
+            );
+        });
+        for xdg in [None, Some(""), Some("relative")] {
+            let vars = [("XDG_CONFIG_HOME", xdg), ("HOME", Some("/home/user"))];
+            crate::test_env::with_vars(&vars, || {
+                assert_eq!(
+                    config_path("mytool", "config.toml"),
+                    PathBuf::from("/home/user/.config/mytool/config.toml")
+                );
+            });
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn test_config_path_macos() {
+        crate::test_env::with_var("HOME", Some("/Users/user"), || {
+            assert_eq!(
+                config_path("mytool", "config.toml"),
+                PathBuf::from("/Users/user/Library/Application Support/mytool/config.toml")
+            );
+        });
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_config_path_windows() {
+        let vars = [("APPDATA", Some(r"C:\Users\user\AppData\Roaming"))];
+        crate::test_env::with_vars(&vars, || {
+            assert_eq!(
+                config_path("mytool", "config.toml"),
+                PathBuf::from(r"C:\Users\user\AppData\Roaming\mytool\config.toml")
+            );
+        });
+    }
+
+    #[test]
+    fn test_pretty_name() {
+        let os_release = "NAME=\"Debian GNU/Linux\"\nPRETTY_NAME=\"Debian GNU/Linux 12 (bookworm)\"\nID=debian\n";
+        assert_eq!(
+            pretty_name(os_release).as_deref(),
+            Some("Debian GNU/Linux 12 (bookworm)")
+        );
+        assert_eq!(pretty_name("ID=alpine\n"), None);
+    }
+}
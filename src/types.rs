@@ -1,5 +1,11 @@
 //! Shared types for Workhelix CLI tools.
 
+use crate::checks::{expand, path_message};
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
 /// Repository information for CLI tools.
 ///
 /// This structure holds basic repository metadata for identification purposes.
@@ -9,35 +15,345 @@ pub struct RepoInfo {
     pub owner: &'static str,
     /// Repository name (e.g., "prompter")
     pub name: &'static str,
+    /// Prefix of release tags (e.g., "v" for `v1.2.3`, "prompter-v" for `prompter-v1.2.3`)
+    pub tag_prefix: &'static str,
+    /// GitHub host (e.g., "github.com", or a GitHub Enterprise host)
+    pub host: &'static str,
 }
 
 impl RepoInfo {
-    /// Create a new `RepoInfo` instance.
+    /// Host of public GitHub.
+    pub const DEFAULT_HOST: &'static str = "github.com";
+
+    /// Create a new `RepoInfo` instance hosted on github.com.
+    #[must_use]
+    pub const fn new(owner: &'static str, name: &'static str, tag_prefix: &'static str) -> Self {
+        Self {
+            owner,
+            name,
+            tag_prefix,
+            host: Self::DEFAULT_HOST,
+        }
+    }
+
+    /// Create a `RepoInfo` for a repository on a GitHub Enterprise host.
+    ///
+    /// Release tags default to the `v` prefix.
+    ///
+    /// # Examples
+    /// ```
+    /// use workhelix_cli_common::RepoInfo;
+    ///
+    /// let repo = RepoInfo::with_host("platform", "mytool", "github.example.com");
+    /// assert_eq!(
+    ///     repo.latest_release_url(),
+    ///     "https://github.example.com/api/v3/repos/platform/mytool/releases/latest"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn with_host(owner: &'static str, name: &'static str, host: &'static str) -> Self {
+        Self {
+            owner,
+            name,
+            tag_prefix: "v",
+            host,
+        }
+    }
+
+    /// Create a `RepoInfo` from a repository URL such as Cargo's `repository` field.
+    ///
+    /// Accepts `https://host/owner/name` (optionally with `.git` or a trailing
+    /// slash), `ssh://git@host/owner/name` and `git@host:owner/name.git`. Returns
+    /// `None` if the URL does not name exactly an owner and a repository.
+    /// Prefer the [`repo_info!`](crate::repo_info) macro, which reads the
+    /// calling crate's manifest.
+    ///
+    /// # Examples
+    /// ```
+    /// use workhelix_cli_common::RepoInfo;
+    ///
+    /// let repo = RepoInfo::from_repository_url("git@github.com:tftio/prompter.git", "v").unwrap();
+    /// assert_eq!((repo.owner, repo.name), ("tftio", "prompter"));
+    /// ```
+    #[must_use]
+    pub fn from_repository_url(url: &'static str, tag_prefix: &'static str) -> Option<Self> {
+        let (host, owner, name) = parse_repository_url(url)?;
+        Some(Self {
+            owner,
+            name,
+            tag_prefix,
+            host,
+        })
+    }
+
+    /// Whether the repository lives on public github.com.
+    fn is_public_github(&self) -> bool {
+        self.host == Self::DEFAULT_HOST
+    }
+
+    /// Release tag for a version, e.g. `1.2.3` → `prompter-v1.2.3`.
+    ///
+    /// A version that already carries the prefix or a leading `v` is not
+    /// prefixed twice.
+    #[must_use]
+    pub fn tag_for(&self, version: &str) -> String {
+        let bare = version.strip_prefix(self.tag_prefix).unwrap_or(version);
+        let bare = bare.strip_prefix('v').unwrap_or(bare);
+        format!("{}{bare}", self.tag_prefix)
+    }
+
+    /// GitHub web URL of the repository.
+    #[must_use]
+    pub fn repo_url(&self) -> String {
+        format!("https://{}/{}/{}", self.host, self.owner, self.name)
+    }
+
+    /// GitHub web URL listing the repository's releases.
+    #[must_use]
+    pub fn releases_url(&self) -> String {
+        format!("{}/releases", self.repo_url())
+    }
+
+    /// GitHub web URL of the release for a version.
+    #[must_use]
+    pub fn release_url_for(&self, version: &str) -> String {
+        format!("{}/tag/{}", self.releases_url(), self.tag_for(version))
+    }
+
+    /// GitHub API URL of the repository.
+    ///
+    /// Uses `api.github.com` for public GitHub and `https://{host}/api/v3` for
+    /// GitHub Enterprise.
+    #[must_use]
+    pub fn api_url(&self) -> String {
+        if self.is_public_github() {
+            format!("https://api.github.com/repos/{}/{}", self.owner, self.name)
+        } else {
+            format!(
+                "https://{}/api/v3/repos/{}/{}",
+                self.host, self.owner, self.name
+            )
+        }
+    }
+
+    /// GitHub API URL for the repository's latest release.
+    #[must_use]
+    pub fn latest_release_url(&self) -> String {
+        format!("{}/releases/latest", self.api_url())
+    }
+
+    /// URL of a raw file in the repository at `git_ref` (a branch, tag or commit).
+    #[must_use]
+    pub fn raw_url(&self, git_ref: &str, path: &str) -> String {
+        if self.is_public_github() {
+            format!(
+                "https://raw.githubusercontent.com/{}/{}/{git_ref}/{path}",
+                self.owner, self.name
+            )
+        } else {
+            format!(
+                "https://{}/raw/{}/{}/{git_ref}/{path}",
+                self.host, self.owner, self.name
+            )
+        }
+    }
+
+    /// URL of the repository's `install.sh` on the `main` branch.
+    #[must_use]
+    pub fn install_script_url(&self) -> String {
+        self.raw_url("main", "install.sh")
+    }
+}
+
+/// Split a repository URL into `(host, owner, name)`.
+fn parse_repository_url(url: &str) -> Option<(&str, &str, &str)> {
+    let url = url.trim();
+    let url = url.strip_prefix("git+").unwrap_or(url);
+    let (host, path) = if let Some((_, rest)) = url.split_once("://") {
+        let rest = rest.split_once('@').map_or(rest, |(user, after)| {
+            // Only a user before the host, not an `@` later in the path
+            if user.contains('/') { rest } else { after }
+        });
+        rest.split_once('/')?
+    } else {
+        // scp-like form: git@host:owner/name.git
+        let (_, rest) = url.split_once('@')?;
+        rest.split_once(':')?
+    };
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, name) = path.split_once('/')?;
+    if host.is_empty() || owner.is_empty() || name.is_empty() || name.contains('/') {
+        return None;
+    }
+    Some((host, owner, name))
+}
+
+/// Severity of a health check result.
+///
+/// Variants are ordered from least to most severe, so the worst of several
+/// results can be found with `Iterator::max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Severity {
+    /// The check passed
+    Pass,
+    /// The check found something worth attention, but not a failure
+    Warn,
+    /// The check failed
+    Fail,
+}
+
+/// Exit codes returned by the doctor and update runners.
+///
+/// The runners return plain `i32` for compatibility; use [`ExitCode::as_i32`]
+/// to compare against or produce them, e.g. `std::process::exit(ExitCode::Success.as_i32())`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum ExitCode {
+    /// The command succeeded (doctor: no failures; update: installed)
+    Success = 0,
+    /// The command failed (doctor: a check failed; update: an error occurred)
+    Failure = 1,
+    /// The update was skipped because the tool is already up-to-date
+    UpToDate = 2,
+    /// The requested update version has no matching release
+    VersionNotFound = 3,
+}
+
+impl ExitCode {
+    /// The integer value passed to `std::process::exit`.
     #[must_use]
-    pub const fn new(owner: &'static str, name: &'static str) -> Self {
-        Self { owner, name }
+    pub const fn as_i32(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<ExitCode> for i32 {
+    fn from(code: ExitCode) -> Self {
+        code.as_i32()
+    }
+}
+
+/// Error returned by the crate's fallible functions.
+///
+/// Module-specific errors such as `update::UpdateError` and
+/// `license::LicenseMismatch` convert into it with `?`, so a tool can handle
+/// every failure from this crate through one type.
+#[derive(Debug)]
+pub enum CommonError {
+    /// Reading or writing a file or stream failed
+    Io(io::Error),
+    /// A network request failed or could not be made
+    Network(String),
+    /// Data could not be parsed (e.g., an API response or version string)
+    Parse(String),
+    /// A requested item does not exist (e.g., a release or file)
+    NotFound(String),
+    /// A version did not match the one required
+    VersionMismatch {
+        /// Version that was required
+        expected: String,
+        /// Version that was found
+        found: String,
+    },
+    /// A license file's text differs from the canonical text of its license
+    LicenseMismatch {
+        /// Line of the file (1-based) where the texts first differ
+        line: usize,
+        /// Canonical text starting at the mismatch
+        expected: String,
+        /// File text starting at the mismatch
+        found: String,
+    },
+}
+
+impl fmt::Display for CommonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+            Self::Network(msg) => write!(f, "network error: {msg}"),
+            Self::Parse(msg) => write!(f, "parse error: {msg}"),
+            Self::NotFound(what) => write!(f, "not found: {what}"),
+            Self::VersionMismatch { expected, found } => {
+                write!(f, "version mismatch: expected {expected}, found {found}")
+            }
+            Self::LicenseMismatch {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "license mismatch at line {line}: expected \"{expected}\", found \"{found}\""
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CommonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CommonError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
     }
 }
 
 /// Health check result for doctor command.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DoctorCheck {
     /// Name of the check
     pub name: String,
-    /// Whether the check passed
+    /// Whether the check passed (`false` only for `Severity::Fail`)
     pub passed: bool,
+    /// Severity of the result
+    pub severity: Severity,
     /// Optional message
     pub message: Option<String>,
+    /// Optional hint telling the user how to fix a failure
+    pub remediation: Option<String>,
+    /// How long the check took, if it was timed
+    pub duration: Option<Duration>,
 }
 
 impl DoctorCheck {
+    /// Start building a check named `name`, which passes unless told otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use workhelix_cli_common::{DoctorCheck, Severity};
+    ///
+    /// let check = DoctorCheck::builder("Database")
+    ///     .passed(false)
+    ///     .message("not initialized")
+    ///     .remediation("mytool init")
+    ///     .build();
+    /// assert_eq!(check.severity, Severity::Fail);
+    /// ```
+    pub fn builder(name: impl Into<String>) -> DoctorCheckBuilder {
+        DoctorCheckBuilder {
+            check: Self::pass(name),
+        }
+    }
+
     /// Create a new passing check.
     #[must_use]
     pub fn pass(name: impl Into<String>) -> Self {
         Self {
             name: name.into(),
             passed: true,
+            severity: Severity::Pass,
             message: None,
+            remediation: None,
+            duration: None,
         }
     }
 
@@ -47,7 +363,71 @@ impl DoctorCheck {
         Self {
             name: name.into(),
             passed: false,
+            severity: Severity::Fail,
+            message: Some(message.into()),
+            remediation: None,
+            duration: None,
+        }
+    }
+
+    /// Create a new failing check with a message and a remediation hint.
+    ///
+    /// The doctor report prints the hint as `→ try: {remediation}` under the failure.
+    #[must_use]
+    pub fn fail_with_fix(
+        name: impl Into<String>,
+        message: impl Into<String>,
+        remediation: impl Into<String>,
+    ) -> Self {
+        Self {
+            remediation: Some(remediation.into()),
+            ..Self::fail(name, message)
+        }
+    }
+
+    /// Run `f` as a check named `name`, recording how long it took.
+    ///
+    /// `Ok` becomes a pass and `Err` a failure with the error as its message.
+    /// The duration is shown by the doctor report when
+    /// [`DoctorOptions::show_timings`](crate::DoctorOptions::show_timings) is set.
+    ///
+    /// # Examples
+    /// ```
+    /// use workhelix_cli_common::DoctorCheck;
+    ///
+    /// let check = DoctorCheck::timed("Config", || std::fs::metadata("Cargo.toml").map(|_| ()));
+    /// assert!(check.passed);
+    /// assert!(check.duration.is_some());
+    /// ```
+    #[must_use]
+    pub fn timed<E: std::fmt::Display>(
+        name: impl Into<String>,
+        f: impl FnOnce() -> Result<(), E>,
+    ) -> Self {
+        let started = Instant::now();
+        let result = f();
+        let check = match result {
+            Ok(()) => Self::pass(name),
+            Err(err) => Self::fail(name, err.to_string()),
+        };
+        Self {
+            duration: Some(started.elapsed()),
+            ..check
+        }
+    }
+
+    /// Create a new warning check with a message.
+    ///
+    /// Warnings are reported but do not count as failures.
+    #[must_use]
+    pub fn warn(name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            passed: true,
+            severity: Severity::Warn,
             message: Some(message.into()),
+            remediation: None,
+            duration: None,
         }
     }
 
@@ -55,14 +435,14 @@ impl DoctorCheck {
     ///
     /// # Errors
     /// Returns a failing check if the file doesn't exist.
-    pub fn file_exists(path: impl AsRef<std::path::Path>) -> Self {
-        let path_ref = path.as_ref();
+    pub fn file_exists(path: impl AsRef<Path>) -> Self {
+        let path_ref = &expand(path.as_ref());
         if path_ref.exists() && path_ref.is_file() {
             Self::pass(format!("File exists: {}", path_ref.display()))
         } else {
             Self::fail(
                 format!("File check: {}", path_ref.display()),
-                format!("File not found: {}", path_ref.display()),
+                path_message("File not found: ", path_ref),
             )
         }
     }
@@ -71,34 +451,361 @@ impl DoctorCheck {
     ///
     /// # Errors
     /// Returns a failing check if the directory doesn't exist.
-    pub fn dir_exists(path: impl AsRef<std::path::Path>) -> Self {
-        let path_ref = path.as_ref();
+    pub fn dir_exists(path: impl AsRef<Path>) -> Self {
+        let path_ref = &expand(path.as_ref());
         if path_ref.exists() && path_ref.is_dir() {
             Self::pass(format!("Directory exists: {}", path_ref.display()))
         } else {
             Self::fail(
                 format!("Directory check: {}", path_ref.display()),
-                format!("Directory not found: {}", path_ref.display()),
+                path_message("Directory not found: ", path_ref),
             )
         }
     }
 }
 
+/// Builder for a [`DoctorCheck`], created by [`DoctorCheck::builder`].
+///
+/// `passed` and `severity` both set the outcome; whichever is called last wins.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct DoctorCheckBuilder {
+    check: DoctorCheck,
+}
+
+impl DoctorCheckBuilder {
+    /// Set whether the check passed: `true` is [`Severity::Pass`], `false` is
+    /// [`Severity::Fail`].
+    pub const fn passed(self, passed: bool) -> Self {
+        self.severity(if passed {
+            Severity::Pass
+        } else {
+            Severity::Fail
+        })
+    }
+
+    /// Set the severity; only [`Severity::Fail`] counts as not passed.
+    pub const fn severity(mut self, severity: Severity) -> Self {
+        self.check.severity = severity;
+        self.check.passed = !matches!(severity, Severity::Fail);
+        self
+    }
+
+    /// Set the message shown under a warning or failure.
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.check.message = Some(message.into());
+        self
+    }
+
+    /// Set the hint telling the user how to fix a failure.
+    pub fn remediation(mut self, remediation: impl Into<String>) -> Self {
+        self.check.remediation = Some(remediation.into());
+        self
+    }
+
+    /// Set how long the check took.
+    pub const fn duration(mut self, duration: Duration) -> Self {
+        self.check.duration = Some(duration);
+        self
+    }
+
+    /// Finish building the check.
+    #[must_use]
+    pub fn build(self) -> DoctorCheck {
+        self.check
+    }
+}
+
+/// A labeled group of health checks (e.g., "Network", "Filesystem").
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DoctorSection {
+    /// Section title shown above its checks
+    pub title: String,
+    /// Checks belonging to this section
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorSection {
+    /// Create a new section.
+    #[must_use]
+    pub fn new(title: impl Into<String>, checks: Vec<DoctorCheck>) -> Self {
+        Self {
+            title: title.into(),
+            checks,
+        }
+    }
+}
+
+/// A node in a tree of health checks, for diagnostics nested deeper than
+/// one level of sections (section → subsection → check).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum DoctorNode {
+    /// A single check result
+    Check(DoctorCheck),
+    /// A titled group of child nodes
+    Section {
+        /// Title shown above the children
+        title: String,
+        /// Checks and subsections belonging to this section
+        children: Vec<Self>,
+    },
+}
+
+impl DoctorNode {
+    /// Create a section node.
+    #[must_use]
+    pub fn section(title: impl Into<String>, children: Vec<Self>) -> Self {
+        Self::Section {
+            title: title.into(),
+            children,
+        }
+    }
+
+    /// Every check in this subtree, depth-first.
+    #[must_use]
+    pub fn checks(&self) -> Vec<&DoctorCheck> {
+        match self {
+            Self::Check(check) => vec![check],
+            Self::Section { children, .. } => children.iter().flat_map(Self::checks).collect(),
+        }
+    }
+}
+
+impl From<DoctorCheck> for DoctorNode {
+    fn from(check: DoctorCheck) -> Self {
+        Self::Check(check)
+    }
+}
+
+impl From<DoctorSection> for DoctorNode {
+    fn from(section: DoctorSection) -> Self {
+        Self::section(
+            section.title,
+            section.checks.into_iter().map(Self::Check).collect(),
+        )
+    }
+}
+
+/// A health check that has not run yet.
+///
+/// Pending checks are executed concurrently by the doctor runner, each on its
+/// own thread, so slow or network-bound checks don't hold up the others. The
+/// name is known up front so a check that never finishes can still be reported.
+pub struct PendingCheck {
+    /// Name reported if the check times out or panics
+    pub name: String,
+    /// The check itself
+    pub run: Box<dyn FnOnce() -> DoctorCheck + Send>,
+}
+
+impl PendingCheck {
+    /// Create a new pending check from a closure.
+    pub fn new(
+        name: impl Into<String>,
+        run: impl FnOnce() -> DoctorCheck + Send + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            run: Box::new(run),
+        }
+    }
+}
+
+impl std::fmt::Debug for PendingCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingCheck")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An async health check that has not run yet.
+///
+/// The async counterpart of [`PendingCheck`]: the doctor runner polls all of
+/// them concurrently, each bounded by the tool's check timeout.
+#[cfg(feature = "tokio")]
+pub struct AsyncCheck<'a> {
+    /// Name reported if the check times out
+    pub name: String,
+    /// The check itself
+    pub run: std::pin::Pin<Box<dyn Future<Output = DoctorCheck> + Send + 'a>>,
+}
+
+#[cfg(feature = "tokio")]
+impl<'a> AsyncCheck<'a> {
+    /// Create a new async check from a future.
+    pub fn new(
+        name: impl Into<String>,
+        run: impl Future<Output = DoctorCheck> + Send + 'a,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            run: Box::pin(run),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl std::fmt::Debug for AsyncCheck<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncCheck")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_exit_code_values() {
+        assert_eq!(ExitCode::Success.as_i32(), 0);
+        assert_eq!(ExitCode::Failure.as_i32(), 1);
+        assert_eq!(ExitCode::UpToDate.as_i32(), 2);
+        assert_eq!(ExitCode::VersionNotFound.as_i32(), 3);
+        assert_eq!(i32::from(ExitCode::Failure), 1);
+    }
+
     #[test]
     fn test_repo_info_creation() {
-        let repo = RepoInfo::new("workhelix", "prompter");
+        let repo = RepoInfo::new("workhelix", "prompter", "prompter-v");
         assert_eq!(repo.owner, "workhelix");
         assert_eq!(repo.name, "prompter");
+        assert_eq!(repo.tag_prefix, "prompter-v");
+    }
+
+    #[test]
+    fn test_repo_info_urls() {
+        let repo = RepoInfo::new("workhelix", "prompter", "prompter-v");
+        assert_eq!(
+            repo.latest_release_url(),
+            "https://api.github.com/repos/workhelix/prompter/releases/latest"
+        );
+        assert_eq!(repo.repo_url(), "https://github.com/workhelix/prompter");
+        assert_eq!(
+            repo.releases_url(),
+            "https://github.com/workhelix/prompter/releases"
+        );
+    }
+
+    #[test]
+    fn test_parse_repository_url() {
+        for url in [
+            "https://github.com/tftio/prompter",
+            "https://github.com/tftio/prompter/",
+            "https://github.com/tftio/prompter.git",
+            "git+https://github.com/tftio/prompter.git",
+            "http://github.com/tftio/prompter",
+            "ssh://git@github.com/tftio/prompter.git",
+            "git@github.com:tftio/prompter.git",
+            "git@github.com:tftio/prompter",
+        ] {
+            assert_eq!(
+                parse_repository_url(url),
+                Some(("github.com", "tftio", "prompter")),
+                "{url}"
+            );
+        }
+        assert_eq!(
+            parse_repository_url("git@github.example.com:platform/mytool.git"),
+            Some(("github.example.com", "platform", "mytool"))
+        );
+        for url in [
+            "",
+            "prompter",
+            "https://github.com/tftio",
+            "https://github.com/tftio/prompter/tree/main",
+            "github.com/tftio/prompter",
+        ] {
+            assert_eq!(parse_repository_url(url), None, "{url}");
+        }
+    }
+
+    #[test]
+    fn test_repo_info_from_repository_url() {
+        let repo = RepoInfo::from_repository_url("https://github.com/tftio/prompter", "v").unwrap();
+        assert_eq!(repo.host, RepoInfo::DEFAULT_HOST);
+        assert_eq!(repo.tag_prefix, "v");
+        assert_eq!(repo.repo_url(), "https://github.com/tftio/prompter");
+
+        let repo = crate::repo_info!();
+        assert_eq!(repo.owner, "tftio");
+        assert_eq!(repo.name, "workhelix-cli-common");
+
+        let repo = crate::repo_info!("workhelix-cli-common-v");
+        assert_eq!(repo.tag_prefix, "workhelix-cli-common-v");
+    }
+
+    #[test]
+    fn test_repo_info_public_host_urls() {
+        let repo = RepoInfo::new("tftio", "peter-hook", "v");
+        assert_eq!(repo.host, "github.com");
+        assert_eq!(
+            repo.api_url(),
+            "https://api.github.com/repos/tftio/peter-hook"
+        );
+        assert_eq!(
+            repo.install_script_url(),
+            "https://raw.githubusercontent.com/tftio/peter-hook/main/install.sh"
+        );
+    }
+
+    #[test]
+    fn test_repo_info_enterprise_host_urls() {
+        let repo = RepoInfo::with_host("platform", "mytool", "github.example.com");
+        assert_eq!(repo.tag_prefix, "v");
+        assert_eq!(
+            repo.repo_url(),
+            "https://github.example.com/platform/mytool"
+        );
+        assert_eq!(
+            repo.latest_release_url(),
+            "https://github.example.com/api/v3/repos/platform/mytool/releases/latest"
+        );
+        assert_eq!(
+            repo.release_url_for("1.0.0"),
+            "https://github.example.com/platform/mytool/releases/tag/v1.0.0"
+        );
+        assert_eq!(
+            repo.install_script_url(),
+            "https://github.example.com/raw/platform/mytool/main/install.sh"
+        );
+    }
+
+    #[test]
+    fn test_release_url_for_v_prefix() {
+        let repo = RepoInfo::new("tftio", "peter-hook", "v");
+        assert_eq!(repo.tag_for("1.2.3"), "v1.2.3");
+        assert_eq!(repo.tag_for("v1.2.3"), "v1.2.3");
+        assert_eq!(
+            repo.release_url_for("1.2.3"),
+            "https://github.com/tftio/peter-hook/releases/tag/v1.2.3"
+        );
+    }
+
+    #[test]
+    fn test_release_url_for_tool_prefix() {
+        let repo = RepoInfo::new("workhelix", "prompter", "prompter-v");
+        assert_eq!(repo.tag_for("0.4.0"), "prompter-v0.4.0");
+        assert_eq!(repo.tag_for("v0.4.0"), "prompter-v0.4.0");
+        // Only one leading `v` is dropped
+        assert_eq!(repo.tag_for("vv0.4.0"), "prompter-vv0.4.0");
+        assert_eq!(repo.tag_for("prompter-v0.4.0"), "prompter-v0.4.0");
+        assert_eq!(
+            repo.release_url_for("0.4.0"),
+            "https://github.com/workhelix/prompter/releases/tag/prompter-v0.4.0"
+        );
     }
 
     #[test]
     fn test_doctor_check_pass() {
         let check = DoctorCheck::pass("test check");
         assert!(check.passed);
+        assert_eq!(check.severity, Severity::Pass);
         assert_eq!(check.name, "test check");
         assert!(check.message.is_none());
     }
@@ -107,7 +814,147 @@ mod tests {
     fn test_doctor_check_fail() {
         let check = DoctorCheck::fail("test check", "error message");
         assert!(!check.passed);
+        assert_eq!(check.severity, Severity::Fail);
         assert_eq!(check.name, "test check");
         assert_eq!(check.message, Some("error message".to_string()));
     }
+
+    #[test]
+    fn test_doctor_check_warn() {
+        let check = DoctorCheck::warn("test check", "warning message");
+        assert!(check.passed);
+        assert_eq!(check.severity, Severity::Warn);
+        assert_eq!(check.message, Some("warning message".to_string()));
+    }
+
+    #[test]
+    fn test_file_and_dir_exists() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("config.toml");
+        std::fs::write(&file, "").unwrap();
+
+        assert!(DoctorCheck::file_exists(&file).passed);
+        assert!(DoctorCheck::dir_exists(dir.path()).passed);
+        assert!(!DoctorCheck::file_exists(dir.path()).passed);
+        assert!(!DoctorCheck::dir_exists(&file).passed);
+        assert!(!DoctorCheck::file_exists(dir.path().join("missing")).passed);
+    }
+
+    #[test]
+    fn test_doctor_check_builder() {
+        let check = DoctorCheck::builder("Database")
+            .passed(false)
+            .message("not initialized")
+            .remediation("mytool init")
+            .duration(Duration::from_millis(42))
+            .build();
+        assert_eq!(check.name, "Database");
+        assert!(!check.passed);
+        assert_eq!(check.severity, Severity::Fail);
+        assert_eq!(check.message.as_deref(), Some("not initialized"));
+        assert_eq!(check.remediation.as_deref(), Some("mytool init"));
+        assert_eq!(check.duration, Some(Duration::from_millis(42)));
+
+        let check = DoctorCheck::builder("Cache")
+            .passed(false)
+            .severity(Severity::Warn)
+            .build();
+        assert!(check.passed);
+        assert_eq!(check.severity, Severity::Warn);
+
+        let check = DoctorCheck::builder("Config").build();
+        assert!(check.passed);
+        assert_eq!(check.severity, Severity::Pass);
+        assert_eq!(check.message, None);
+        assert_eq!(check.duration, None);
+    }
+
+    #[test]
+    fn test_doctor_check_timed() {
+        let check = DoctorCheck::timed("sleepy", || {
+            std::thread::sleep(Duration::from_millis(10));
+            Ok::<(), String>(())
+        });
+        assert!(check.passed);
+        assert!(check.duration.unwrap() >= Duration::from_millis(10));
+
+        let check = DoctorCheck::timed("broken", || Err("no config"));
+        assert_eq!(check.severity, Severity::Fail);
+        assert_eq!(check.message.as_deref(), Some("no config"));
+        assert!(check.duration.is_some());
+    }
+
+    #[test]
+    fn test_doctor_section_new() {
+        let section = DoctorSection::new("Network", vec![DoctorCheck::pass("ping")]);
+        assert_eq!(section.title, "Network");
+        assert_eq!(section.checks.len(), 1);
+    }
+
+    #[test]
+    fn test_common_error_display() {
+        let io = CommonError::from(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+        assert_eq!(io.to_string(), "I/O error: no such file");
+        assert!(std::error::Error::source(&io).is_some());
+        assert_eq!(
+            CommonError::Network("connection refused".into()).to_string(),
+            "network error: connection refused"
+        );
+        assert_eq!(
+            CommonError::Parse("unexpected token".into()).to_string(),
+            "parse error: unexpected token"
+        );
+        assert_eq!(
+            CommonError::NotFound("release v9.9.9".into()).to_string(),
+            "not found: release v9.9.9"
+        );
+        let mismatch = CommonError::VersionMismatch {
+            expected: "2.0.0".into(),
+            found: "1.4.2".into(),
+        };
+        assert_eq!(
+            mismatch.to_string(),
+            "version mismatch: expected 2.0.0, found 1.4.2"
+        );
+        assert!(std::error::Error::source(&mismatch).is_none());
+        assert_eq!(
+            CommonError::LicenseMismatch {
+                line: 3,
+                expected: "free of charge".into(),
+                found: "for a fee".into(),
+            }
+            .to_string(),
+            "license mismatch at line 3: expected \"free of charge\", found \"for a fee\""
+        );
+    }
+
+    #[test]
+    fn test_doctor_node_checks() {
+        let tree = DoctorNode::section(
+            "Storage",
+            vec![
+                DoctorCheck::pass("mounted").into(),
+                DoctorSection::new("Cache", vec![DoctorCheck::fail("writable", "denied")]).into(),
+            ],
+        );
+        let names: Vec<_> = tree
+            .checks()
+            .iter()
+            .map(|check| check.name.as_str())
+            .collect();
+        assert_eq!(names, ["mounted", "writable"]);
+    }
+
+    #[test]
+    fn test_pending_check_runs_closure() {
+        let pending = PendingCheck::new("deferred", || DoctorCheck::pass("deferred"));
+        assert_eq!(pending.name, "deferred");
+        assert!((pending.run)().passed);
+    }
+
+    #[test]
+    fn test_severity_ordering() {
+        assert!(Severity::Pass < Severity::Warn);
+        assert!(Severity::Warn < Severity::Fail);
+    }
 }
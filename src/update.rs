@@ -7,24 +7,72 @@
 //! - Verifying checksums (mandatory)
 //! - Version comparison and upgrade logic
 //! - Replacing the current binary
+//!
+//! With the `self-update` feature, [`run_update_native`] performs the same steps
+//! in-process without piping a remote script to a shell.
 
-use crate::types::RepoInfo;
+#[cfg(feature = "http")]
+use crate::types::CommonError;
+use crate::types::{ExitCode, RepoInfo};
+#[cfg(feature = "self-update")]
+use std::collections::HashMap;
+use std::ffi::OsString;
 use std::path::Path;
-use std::process::Command;
+#[cfg(feature = "self-update")]
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether network access is disabled; see [`set_offline`].
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable offline mode for air-gapped environments.
+///
+/// While offline, [`run_update`] and `run_update_native` fail immediately
+/// with an "offline mode" message, `check_for_update` returns
+/// `UpdateError::Offline`, and `DoctorCheck::http_reachable` reports a
+/// "Skipped (offline mode)" warning instead of waiting for a timeout.
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+/// Whether offline mode is enabled; see [`set_offline`].
+#[must_use]
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Report that an update cannot run offline, returning the exit code.
+fn offline_exit() -> Option<i32> {
+    if !is_offline() {
+        return None;
+    }
+    eprintln!("❌ Cannot update in offline mode");
+    eprintln!("   Disable offline mode to download releases");
+    Some(ExitCode::Failure.as_i32())
+}
 
 /// Run update command to install latest or specified version.
 ///
 /// This delegates to the install.sh script, which handles version checking,
 /// download, checksum verification, and installation.
 ///
-/// Returns exit code: 0 if successful, 1 on error, 2 if already up-to-date.
+/// When a specific version is requested, its release is looked up first and
+/// passed to the install script via the `VERSION` and `RELEASE_URL` variables.
+///
+/// Returns exit code: [`ExitCode::Success`] (0) if successful, [`ExitCode::Failure`] (1)
+/// on error or in offline mode, [`ExitCode::UpToDate`] (2) if already up-to-date, or
+/// [`ExitCode::VersionNotFound`] (3) if the requested version does not exist.
 ///
 /// # Arguments
 /// * `repo_info` - Repository information for GitHub integration
 /// * `_current_version` - Current version of the tool (unused, install.sh detects this)
-/// * `version` - Optional specific version to install (currently unsupported, always installs latest)
+/// * `version` - Optional specific version to install (e.g. `1.2.3` or `v1.2.3`); latest if `None`
 /// * `force` - Force reinstall even if already up-to-date
 /// * `install_dir` - Optional custom installation directory
+/// * `extra_args` - Extra arguments passed through to the install script
+///   (via `sh -s --`); each is shell-quoted, so spaces and metacharacters are
+///   passed literally
 ///
 /// # Panics
 /// May panic if stdout flush fails during user interaction.
@@ -35,64 +83,770 @@ pub fn run_update(
     version: Option<&str>,
     force: bool,
     install_dir: Option<&Path>,
+    extra_args: &[&str],
 ) -> i32 {
-    if version.is_some() {
-        eprintln!("⚠️  Specific version installation not yet supported");
-        eprintln!("   The install script will install the latest version");
-        println!();
+    if let Some(code) = offline_exit() {
+        return code;
     }
 
+    let pinned = match version {
+        Some(version) => match resolve_version(repo_info, version, release_exists) {
+            Ok(pinned) => Some(pinned),
+            Err(code) => return code,
+        },
+        None => None,
+    };
+
     println!("🔄 Running installation script...");
     println!();
 
-    // Build install.sh URL
-    let install_script_url = format!(
-        "https://raw.githubusercontent.com/{}/{}/main/install.sh",
-        repo_info.owner, repo_info.name
-    );
-
-    // Build command to download and execute install script
+    // Settings reach the script through the process environment, never the
+    // command string, so paths with spaces or metacharacters stay literal.
     let mut cmd = Command::new("sh");
-    cmd.arg("-c");
+    cmd.arg("-c")
+        .arg(install_command(&repo_info.install_script_url(), extra_args))
+        .envs(install_env(repo_info, pinned.as_ref(), force, install_dir));
 
-    // Build the command string with environment variables
-    let mut env_vars = Vec::new();
-    env_vars.push(format!("REPO_OWNER={}", repo_info.owner));
-    env_vars.push(format!("REPO_NAME={}", repo_info.name));
+    // Execute the command
+    match cmd.status() {
+        Ok(status) => {
+            if status.success() {
+                ExitCode::Success.as_i32()
+            } else {
+                status.code().unwrap_or(ExitCode::Failure.as_i32())
+            }
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to run install script: {e}");
+            eprintln!("   Make sure curl is installed and you have internet access");
+            ExitCode::Failure.as_i32()
+        }
+    }
+}
 
+/// Environment variables that configure the install script.
+fn install_env(
+    repo_info: &RepoInfo,
+    pinned: Option<&(String, String)>,
+    force: bool,
+    install_dir: Option<&Path>,
+) -> Vec<(&'static str, OsString)> {
+    let mut env = vec![
+        ("REPO_OWNER", repo_info.owner.into()),
+        ("REPO_NAME", repo_info.name.into()),
+    ];
+    if repo_info.host != RepoInfo::DEFAULT_HOST {
+        env.push(("GITHUB_HOST", repo_info.host.into()));
+    }
+    if let Some((tag, url)) = pinned {
+        env.push(("VERSION", tag.into()));
+        env.push(("RELEASE_URL", url.into()));
+    }
     if force {
-        env_vars.push("FORCE_INSTALL=1".to_string());
+        env.push(("FORCE_INSTALL", "1".into()));
     }
-
     if let Some(dir) = install_dir {
-        env_vars.push(format!("INSTALL_DIR={}", dir.display()));
+        env.push(("INSTALL_DIR", dir.into()));
     }
+    env
+}
 
-    let env_string = env_vars.join(" ");
-    let command_string = format!("{env_string} curl -fsSL {install_script_url} | sh");
+/// Build the `curl | sh` pipeline, forwarding `extra_args` to the script.
+///
+/// Every interpolated value is shell-quoted.
+fn install_command(script_url: &str, extra_args: &[&str]) -> String {
+    let mut command = format!("curl -fsSL {} | sh", shell_quote(script_url));
+    if !extra_args.is_empty() {
+        command.push_str(" -s --");
+        for arg in extra_args {
+            command.push(' ');
+            command.push_str(&shell_quote(arg));
+        }
+    }
+    command
+}
 
-    cmd.arg(&command_string);
+/// Quote `s` for POSIX `sh` so it is passed as a single literal word.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
 
-    // Execute the command
-    match cmd.status() {
-        Ok(status) => {
-            if status.success() {
-                0
+/// GitHub API URL for the release tagged `tag`.
+fn release_tag_url(repo_info: &RepoInfo, tag: &str) -> String {
+    format!("{}/releases/tags/{tag}", repo_info.api_url())
+}
+
+/// Look up the release for `version`, returning its tag and API URL.
+///
+/// Reports the error and returns the exit code if the release does not exist
+/// or the lookup itself fails.
+fn resolve_version(
+    repo_info: &RepoInfo,
+    version: &str,
+    exists: impl Fn(&str) -> Result<bool, String>,
+) -> Result<(String, String), i32> {
+    let tag = repo_info.tag_for(version);
+    let url = release_tag_url(repo_info, &tag);
+    match exists(&url) {
+        Ok(true) => Ok((tag, url)),
+        Ok(false) => {
+            eprintln!("❌ Version {version} not found (no release tagged {tag})");
+            eprintln!("   See {} for available versions", repo_info.releases_url());
+            Err(ExitCode::VersionNotFound.as_i32())
+        }
+        Err(reason) => {
+            eprintln!("❌ Cannot look up version {version}: {reason}");
+            Err(ExitCode::Failure.as_i32())
+        }
+    }
+}
+
+/// Check whether a URL resolves, using curl like the install script does.
+///
+/// Returns `Ok(false)` only for an HTTP 404; any other failure is an error
+/// describing what went wrong.
+fn release_exists(url: &str) -> Result<bool, String> {
+    let output = Command::new("curl")
+        .args(["-sSI", "-o", "/dev/null", "-w", "%{http_code}", url])
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("cannot run curl: {e}"))?;
+    // curl writes 000 when no HTTP response was received
+    let code: u16 = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .unwrap_or(0);
+    match code {
+        404 => Ok(false),
+        100..400 => Ok(true),
+        0 => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr.trim();
+            Err(if reason.is_empty() {
+                format!("curl exited with {}", output.status)
             } else {
-                status.code().unwrap_or(1)
-            }
+                reason.to_string()
+            })
         }
+        code => Err(format!("HTTP {code}")),
+    }
+}
+
+/// Result of comparing the running version with the latest release.
+#[cfg(feature = "http")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateStatus {
+    /// The running version is the latest release
+    UpToDate,
+    /// A newer release is available
+    Available {
+        /// Version of the latest release, without any tag prefix
+        latest: String,
+    },
+    /// The running version is newer than the latest release (e.g. a dev build)
+    Ahead,
+}
+
+/// Error from querying or comparing releases.
+#[cfg(feature = "http")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateError {
+    /// The request to the releases API failed
+    Network(String),
+    /// The releases API returned something unexpected
+    InvalidResponse(String),
+    /// A version string was not valid semver
+    InvalidVersion(String),
+    /// Offline mode is enabled, so no request was made
+    Offline,
+}
+
+#[cfg(feature = "http")]
+impl std::fmt::Display for UpdateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Network(msg) | Self::InvalidResponse(msg) => f.write_str(msg),
+            Self::InvalidVersion(version) => write!(f, "invalid version: {version}"),
+            Self::Offline => f.write_str("offline mode: update check skipped"),
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+impl std::error::Error for UpdateError {}
+
+#[cfg(feature = "http")]
+impl From<UpdateError> for CommonError {
+    fn from(e: UpdateError) -> Self {
+        match e {
+            UpdateError::Network(msg) => Self::Network(msg),
+            UpdateError::Offline => Self::Network(e.to_string()),
+            UpdateError::InvalidResponse(msg) => Self::Parse(msg),
+            UpdateError::InvalidVersion(_) => Self::Parse(e.to_string()),
+        }
+    }
+}
+
+/// Check whether a newer release is available, without installing anything.
+///
+/// Queries the GitHub releases API for the latest release and compares its
+/// version with `current_version` using semver. If the `GITHUB_TOKEN`
+/// environment variable is set, it is sent to raise the API rate limit.
+///
+/// # Errors
+/// Returns an error if the API request fails, the response cannot be parsed,
+/// or either version is not valid semver. Returns [`UpdateError::Offline`]
+/// without making a request if offline mode is enabled. The error converts
+/// into [`CommonError`] with `?`.
+///
+/// # Examples
+/// ```no_run
+/// use workhelix_cli_common::RepoInfo;
+/// use workhelix_cli_common::update::{UpdateStatus, check_for_update};
+///
+/// let repo = RepoInfo::new("myorg", "mytool", "v");
+/// match check_for_update(&repo, env!("CARGO_PKG_VERSION")) {
+///     Ok(UpdateStatus::Available { latest }) => println!("Update available: {latest}"),
+///     Ok(_) => println!("Up to date"),
+///     Err(e) => eprintln!("Could not check for updates: {e}"),
+/// }
+/// ```
+#[cfg(feature = "http")]
+pub fn check_for_update(
+    repo: &RepoInfo,
+    current_version: &str,
+) -> Result<UpdateStatus, UpdateError> {
+    if is_offline() {
+        return Err(UpdateError::Offline);
+    }
+    let user_agent = format!("{}/{current_version}", repo.name);
+    let release = fetch_latest_release(&http_agent(), repo, &user_agent)?;
+    update_status(current_version, release_version(repo, &release.tag))
+}
+
+/// Fetch and parse the repository's latest release.
+#[cfg(feature = "http")]
+fn fetch_latest_release(
+    agent: &ureq::Agent,
+    repo: &RepoInfo,
+    user_agent: &str,
+) -> Result<Release, UpdateError> {
+    let body = http_get(agent, &repo.latest_release_url(), user_agent, true)?;
+    parse_release(&String::from_utf8_lossy(&body))
+}
+
+/// Compare the running version with the latest release version.
+#[cfg(feature = "http")]
+fn update_status(current: &str, latest: &str) -> Result<UpdateStatus, UpdateError> {
+    for version in [current, latest] {
+        if crate::version::parse_version(version).is_none() {
+            return Err(UpdateError::InvalidVersion(version.to_string()));
+        }
+    }
+    let ordering = crate::version::compare_versions(latest, current)
+        .ok_or_else(|| UpdateError::InvalidVersion(latest.to_string()))?;
+    Ok(match ordering {
+        std::cmp::Ordering::Greater => UpdateStatus::Available {
+            latest: latest.trim_start_matches('v').to_string(),
+        },
+        std::cmp::Ordering::Equal => UpdateStatus::UpToDate,
+        std::cmp::Ordering::Less => UpdateStatus::Ahead,
+    })
+}
+
+/// Options for [`run_update_native`].
+#[cfg(feature = "self-update")]
+#[derive(Debug, Clone, Default)]
+pub struct UpdateOptions {
+    /// Reinstall even if already up-to-date
+    pub force: bool,
+    /// Binary to replace (defaults to the currently running executable)
+    pub target: Option<PathBuf>,
+}
+
+/// Update the running binary natively, without shelling out to an install script.
+///
+/// Queries the GitHub releases API for the latest release, selects the asset
+/// built for the current target triple, downloads it, verifies its SHA-256
+/// checksum against the checksum published with the release, and atomically
+/// replaces the binary. A release without a checksum for the asset is rejected.
+///
+/// With the `archive-tar` or `archive-zip` features, `.tar.gz` and `.zip`
+/// assets are also accepted; the executable named after the repository is
+/// extracted from the archive after the checksum is verified.
+///
+/// Returns exit code: [`ExitCode::Success`] (0) if successful, [`ExitCode::Failure`] (1)
+/// on error or in offline mode, or [`ExitCode::UpToDate`] (2) if already up-to-date.
+///
+/// # Arguments
+/// * `repo` - Repository information for GitHub integration
+/// * `current_version` - Current version of the tool
+/// * `opts` - Update options
+///
+/// # Examples
+/// ```no_run
+/// use workhelix_cli_common::{RepoInfo, UpdateOptions, run_update_native};
+///
+/// let repo = RepoInfo::new("myorg", "mytool", "v");
+/// let exit_code = run_update_native(&repo, env!("CARGO_PKG_VERSION"), UpdateOptions::default());
+/// std::process::exit(exit_code);
+/// ```
+#[cfg(feature = "self-update")]
+#[must_use]
+pub fn run_update_native(repo: &RepoInfo, current_version: &str, opts: UpdateOptions) -> i32 {
+    if let Some(code) = offline_exit() {
+        return code;
+    }
+    match native_update(repo, current_version, opts) {
+        Ok(true) => ExitCode::Success.as_i32(),
+        Ok(false) => ExitCode::UpToDate.as_i32(),
         Err(e) => {
-            eprintln!("❌ Failed to run install script: {e}");
-            eprintln!("   Make sure curl is installed and you have internet access");
-            1
+            eprintln!("❌ Update failed: {e}");
+            ExitCode::Failure.as_i32()
+        }
+    }
+}
+
+/// Perform the native update, returning `Ok(false)` when already up-to-date.
+#[cfg(feature = "self-update")]
+fn native_update(
+    repo: &RepoInfo,
+    current_version: &str,
+    opts: UpdateOptions,
+) -> Result<bool, String> {
+    let target = match opts.target {
+        Some(path) => path,
+        None => {
+            std::env::current_exe().map_err(|e| format!("cannot locate current executable: {e}"))?
+        }
+    };
+    let agent = http_agent();
+    let user_agent = format!("{}/{current_version}", repo.name);
+
+    println!("🔍 Checking for the latest release...");
+    let release = fetch_latest_release(&agent, repo, &user_agent).map_err(|e| e.to_string())?;
+    let latest = release_version(repo, &release.tag);
+    let status = update_status(current_version, latest).map_err(|e| e.to_string())?;
+
+    if !opts.force && !matches!(status, UpdateStatus::Available { .. }) {
+        println!("✅ Already up-to-date ({current_version})");
+        return Ok(false);
+    }
+
+    let triple = crate::platform::target_triple();
+    let asset = select_asset(&release.assets, &triple)
+        .ok_or_else(|| format!("no release asset found for {triple}"))?;
+    let checksum_asset = find_checksum_asset(&release.assets, &asset.name)
+        .ok_or_else(|| format!("no SHA-256 checksum published for {}", asset.name))?;
+
+    println!("⬇️  Downloading {}...", asset.name);
+    let data = http_get(&agent, &asset.url, &user_agent, false).map_err(|e| e.to_string())?;
+    let checksums =
+        http_get(&agent, &checksum_asset.url, &user_agent, false).map_err(|e| e.to_string())?;
+    let expected =
+        parse_checksum(&String::from_utf8_lossy(&checksums), &asset.name).ok_or_else(|| {
+            format!(
+                "checksum for {} not found in {}",
+                asset.name, checksum_asset.name
+            )
+        })?;
+    if !verify_sha256(&data, &expected) {
+        return Err(format!("checksum mismatch for {}", asset.name));
+    }
+
+    let bin_name = format!("{}{}", repo.name, std::env::consts::EXE_SUFFIX);
+    let binary = extract_binary(&asset.name, data, &bin_name)?;
+    install_binary(&binary, &target)
+        .map_err(|e| format!("failed to install {}: {e}", target.display()))?;
+    println!(
+        "✨ Updated {} from {current_version} to {latest}",
+        repo.name
+    );
+    Ok(true)
+}
+
+/// Maximum size of a downloaded release asset.
+#[cfg(feature = "http")]
+const MAX_DOWNLOAD_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Build the HTTP agent used for release queries and downloads.
+#[cfg(feature = "http")]
+fn http_agent() -> ureq::Agent {
+    ureq::Agent::config_builder()
+        .timeout_connect(Some(std::time::Duration::from_secs(30)))
+        .build()
+        .into()
+}
+
+/// Fetch `url`, returning the response body.
+///
+/// `authenticate` sends `GITHUB_TOKEN`; it is only set for API requests so the
+/// token is never sent to asset download hosts.
+#[cfg(feature = "http")]
+fn http_get(
+    agent: &ureq::Agent,
+    url: &str,
+    user_agent: &str,
+    authenticate: bool,
+) -> Result<Vec<u8>, UpdateError> {
+    let mut request = agent.get(url).header("User-Agent", user_agent);
+    if authenticate {
+        if let Some(token) = github_token() {
+            request = request.header("Authorization", format!("Bearer {token}"));
         }
     }
+    request
+        .call()
+        .map_err(|e| UpdateError::Network(format!("request to {url} failed: {e}")))?
+        .body_mut()
+        .with_config()
+        .limit(MAX_DOWNLOAD_BYTES)
+        .read_to_vec()
+        .map_err(|e| UpdateError::Network(format!("failed to read response from {url}: {e}")))
+}
+
+/// GitHub token from `GITHUB_TOKEN`, used to raise API rate limits.
+#[cfg(feature = "http")]
+fn github_token() -> Option<String> {
+    std::env::var("GITHUB_TOKEN")
+        .ok()
+        .filter(|token| !token.trim().is_empty())
+}
+
+/// A downloadable file attached to a release.
+#[cfg(feature = "http")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ReleaseAsset {
+    name: String,
+    url: String,
+}
+
+/// The parts of a GitHub release needed for updating.
+#[cfg(feature = "http")]
+#[derive(Debug)]
+struct Release {
+    tag: String,
+    // Only needed for downloading, which requires `self-update`
+    #[cfg_attr(not(feature = "self-update"), allow(dead_code))]
+    assets: Vec<ReleaseAsset>,
+}
+
+/// Parse a GitHub releases API response.
+#[cfg(feature = "http")]
+fn parse_release(json: &str) -> Result<Release, UpdateError> {
+    let value: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| UpdateError::InvalidResponse(format!("invalid release response: {e}")))?;
+    let tag = value["tag_name"]
+        .as_str()
+        .ok_or_else(|| UpdateError::InvalidResponse("release response has no tag_name".into()))?
+        .to_string();
+    let assets = value["assets"]
+        .as_array()
+        .map(|assets| {
+            assets
+                .iter()
+                .filter_map(|asset| {
+                    Some(ReleaseAsset {
+                        name: asset["name"].as_str()?.to_string(),
+                        url: asset["browser_download_url"].as_str()?.to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(Release { tag, assets })
+}
+
+/// Strip the repository's tag prefix (e.g. `v` or `mytool-v`) to get the bare version.
+#[cfg(feature = "http")]
+fn release_version<'a>(repo: &RepoInfo, tag: &'a str) -> &'a str {
+    tag.strip_prefix(repo.tag_prefix)
+        .unwrap_or(tag)
+        .trim_start_matches('v')
+}
+
+/// Whether an asset name refers to a checksum file rather than a binary.
+#[cfg(feature = "self-update")]
+fn is_checksum_file(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    lower.ends_with(".sha256") || lower.ends_with(".sha256sum") || is_checksums_list(name)
+}
+
+/// Whether an asset name refers to a combined checksums file for all assets.
+#[cfg(feature = "self-update")]
+fn is_checksums_list(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    lower.contains("sha256sums") || lower == "checksums.txt"
+}
+
+/// Whether an asset name refers to an archive rather than a bare binary.
+#[cfg(feature = "self-update")]
+fn is_archive(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    [".tar.gz", ".tgz", ".tar.xz", ".zip"]
+        .iter()
+        .any(|ext| lower.ends_with(ext))
+}
+
+/// Whether an asset name refers to a `.tar.gz` archive.
+#[cfg(feature = "self-update")]
+fn is_tar_gz(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    [".tar.gz", ".tgz"].iter().any(|ext| lower.ends_with(ext))
+}
+
+/// Whether an asset name refers to a `.zip` archive.
+#[cfg(feature = "self-update")]
+fn is_zip(name: &str) -> bool {
+    name.to_ascii_lowercase().ends_with(".zip")
+}
+
+/// Whether the native updater can unpack an archive with this name.
+#[cfg(feature = "self-update")]
+fn is_supported_archive(name: &str) -> bool {
+    (cfg!(feature = "archive-tar") && is_tar_gz(name))
+        || (cfg!(feature = "archive-zip") && is_zip(name))
+}
+
+/// Select the asset built for `target`.
+///
+/// A bare binary is preferred over an archive; archives are only considered
+/// when the matching `archive-*` feature is enabled. On glibc Linux, a
+/// statically linked musl build is accepted as a fallback.
+#[cfg(feature = "self-update")]
+fn select_asset<'a>(assets: &'a [ReleaseAsset], target: &str) -> Option<&'a ReleaseAsset> {
+    let fallback = target.replace("-linux-gnu", "-linux-musl");
+    [target, fallback.as_str()].into_iter().find_map(|triple| {
+        let candidates = || {
+            assets
+                .iter()
+                .filter(move |asset| asset.name.contains(triple) && !is_checksum_file(&asset.name))
+        };
+        candidates()
+            .find(|asset| !is_archive(&asset.name))
+            .or_else(|| candidates().find(|asset| is_supported_archive(&asset.name)))
+    })
+}
+
+/// Extract the executable named `bin_name` from a downloaded asset.
+///
+/// Bare binaries are returned unchanged. Archives are searched for a regular
+/// file whose name is `bin_name`, wherever it sits in the archive.
+#[cfg(feature = "self-update")]
+fn extract_binary(asset_name: &str, data: Vec<u8>, bin_name: &str) -> Result<Vec<u8>, String> {
+    if !is_archive(asset_name) {
+        return Ok(data);
+    }
+    #[cfg(feature = "archive-tar")]
+    if is_tar_gz(asset_name) {
+        return extract_from_tar_gz(&data, bin_name)
+            .map_err(|e| format!("cannot read {asset_name}: {e}"))?
+            .ok_or_else(|| format!("{bin_name} not found in {asset_name}"));
+    }
+    #[cfg(feature = "archive-zip")]
+    if is_zip(asset_name) {
+        return extract_from_zip(&data, bin_name)
+            .map_err(|e| format!("cannot read {asset_name}: {e}"))?
+            .ok_or_else(|| format!("{bin_name} not found in {asset_name}"));
+    }
+    let _ = bin_name;
+    Err(format!("unsupported archive format: {asset_name}"))
+}
+
+/// Whether an archive entry path names the executable.
+#[cfg(any(feature = "archive-tar", feature = "archive-zip"))]
+fn is_binary_entry(path: &Path, bin_name: &str) -> bool {
+    path.file_name().is_some_and(|name| name == bin_name)
+}
+
+/// Read the first regular file named `bin_name` from a gzip-compressed tarball.
+#[cfg(feature = "archive-tar")]
+fn extract_from_tar_gz(data: &[u8], bin_name: &str) -> std::io::Result<Option<Vec<u8>>> {
+    use std::io::Read;
+
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(data));
+    for entry in archive.entries()? {
+        let entry = entry?;
+        if entry.header().entry_type().is_file() && is_binary_entry(&entry.path()?, bin_name) {
+            let mut binary = Vec::new();
+            entry.take(MAX_DOWNLOAD_BYTES).read_to_end(&mut binary)?;
+            return Ok(Some(binary));
+        }
+    }
+    Ok(None)
+}
+
+/// Read the first regular file named `bin_name` from a zip archive.
+#[cfg(feature = "archive-zip")]
+fn extract_from_zip(data: &[u8], bin_name: &str) -> std::io::Result<Option<Vec<u8>>> {
+    use std::io::Read;
+
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(data))?;
+    for index in 0..archive.len() {
+        let file = archive.by_index(index)?;
+        let matches = file
+            .enclosed_name()
+            .is_some_and(|path| is_binary_entry(&path, bin_name));
+        if file.is_file() && matches {
+            let mut binary = Vec::new();
+            file.take(MAX_DOWNLOAD_BYTES).read_to_end(&mut binary)?;
+            return Ok(Some(binary));
+        }
+    }
+    Ok(None)
+}
+
+/// Find the checksum file covering `asset_name`: a per-asset `.sha256` file,
+/// or a combined checksums file.
+#[cfg(feature = "self-update")]
+fn find_checksum_asset<'a>(
+    assets: &'a [ReleaseAsset],
+    asset_name: &str,
+) -> Option<&'a ReleaseAsset> {
+    let per_asset = [
+        format!("{asset_name}.sha256"),
+        format!("{asset_name}.sha256sum"),
+    ];
+    assets
+        .iter()
+        .find(|asset| per_asset.contains(&asset.name))
+        .or_else(|| assets.iter().find(|asset| is_checksums_list(&asset.name)))
+}
+
+/// Extract the expected SHA-256 hex digest for `asset_name` from a checksum file.
+///
+/// Accepts a bare digest, or `sha256sum`-style `HASH  filename` lines (with an
+/// optional `*` binary marker).
+#[cfg(feature = "self-update")]
+fn parse_checksum(contents: &str, asset_name: &str) -> Option<String> {
+    let is_digest = |s: &str| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit());
+    let entries: Vec<_> = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if let [only] = entries.as_slice() {
+        let mut parts = only.split_whitespace();
+        if let (Some(hash), None) = (parts.next(), parts.next()) {
+            return is_digest(hash).then(|| hash.to_ascii_lowercase());
+        }
+    }
+
+    parse_checksums_file(contents).remove(asset_name)
+}
+
+/// Parse a `sha256sum`-style checksums file into a map of file name to digest.
+///
+/// Each line has the form `HASH  filename`; a `*` binary marker before the
+/// file name is ignored. Digests are lowercased, and lines that are not a
+/// 64-digit hex digest followed by a file name are skipped.
+///
+/// # Examples
+/// ```
+/// use workhelix_cli_common::update::parse_checksums_file;
+///
+/// let sums = format!("{}  mytool-linux\n{} *mytool.exe\n", "A".repeat(64), "b".repeat(64));
+/// let checksums = parse_checksums_file(&sums);
+/// assert_eq!(checksums["mytool-linux"], "a".repeat(64));
+/// assert_eq!(checksums["mytool.exe"], "b".repeat(64));
+/// ```
+#[cfg(feature = "self-update")]
+#[must_use]
+pub fn parse_checksums_file(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (hash, name) = line.trim().split_once(char::is_whitespace)?;
+            let name = name.trim_start().trim_start_matches('*');
+            let is_digest = hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit());
+            (is_digest && !name.is_empty()).then(|| (name.to_string(), hash.to_ascii_lowercase()))
+        })
+        .collect()
+}
+
+/// Compute the lowercase hex SHA-256 digest of `data`.
+#[cfg(feature = "self-update")]
+fn sha256_hex(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    use std::fmt::Write as _;
+
+    Sha256::digest(data)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// Whether `data` hashes to the `expected_hex` SHA-256 digest.
+///
+/// The comparison is case-insensitive and ignores surrounding whitespace.
+///
+/// # Examples
+/// ```
+/// use workhelix_cli_common::update::verify_sha256;
+///
+/// let hash = "B94D27B9934D3E08A52E52D7DA7DABFAC484EFE37A5380EE9088F7ACE2EFCDE9";
+/// assert!(verify_sha256(b"hello world", hash));
+/// assert!(!verify_sha256(b"hello world!", hash));
+/// ```
+#[cfg(feature = "self-update")]
+#[must_use]
+pub fn verify_sha256(data: &[u8], expected_hex: &str) -> bool {
+    sha256_hex(data).eq_ignore_ascii_case(expected_hex.trim())
+}
+
+/// Atomically replace the binary at `target` with `data`.
+///
+/// The new binary is written to a temporary file next to `target` and renamed
+/// over it, so the target is never left partially written. On Windows, where
+/// a running executable cannot be overwritten, the old binary is first moved
+/// aside to `<target>.old` (e.g. `mytool.exe.old`) and moved back if the new
+/// binary cannot be renamed into place.
+#[cfg(feature = "self-update")]
+fn install_binary(data: &[u8], target: &Path) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let dir = target.parent().unwrap_or_else(|| Path::new("."));
+    let file_name = target
+        .file_name()
+        .map_or_else(|| "binary".into(), |name| name.to_string_lossy());
+    let temp = dir.join(format!(".{file_name}.update-{}", std::process::id()));
+
+    let result = (|| {
+        let mut file = std::fs::File::create(&temp)?;
+        file.write_all(data)?;
+        file.sync_all()?;
+        drop(file);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&temp, std::fs::Permissions::from_mode(0o755))?;
+        }
+
+        if cfg!(windows) && target.exists() {
+            let old = dir.join(format!("{file_name}.old"));
+            std::fs::rename(target, &old)?;
+            return std::fs::rename(&temp, target).inspect_err(|_| {
+                // Put the running binary back so the tool stays installed
+                let _ = std::fs::rename(&old, target);
+            });
+        }
+        std::fs::rename(&temp, target)
+    })();
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp);
+    }
+    result
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_env::offline_lock;
 
     #[test]
     fn test_repo_info_latest_release_url() {
@@ -104,14 +858,456 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_release_tag_url_construction() {
+        let repo = RepoInfo::new("tftio", "peter-hook", "v");
+        let (tag, url) = resolve_version(&repo, "v1.2.3", |_| Ok(true)).unwrap();
+        assert_eq!(tag, "v1.2.3");
+        assert_eq!(
+            url,
+            "https://api.github.com/repos/tftio/peter-hook/releases/tags/v1.2.3"
+        );
+
+        let repo = RepoInfo::new("workhelix", "prompter", "prompter-v");
+        let (tag, _) = resolve_version(&repo, "1.2.3", |_| Ok(true)).unwrap();
+        assert_eq!(tag, "prompter-v1.2.3");
+
+        let repo = RepoInfo::with_host("workhelix", "prompter", "git.example.com");
+        let (_, url) = resolve_version(&repo, "1.2.3", |_| Ok(true)).unwrap();
+        assert_eq!(
+            url,
+            "https://git.example.com/api/v3/repos/workhelix/prompter/releases/tags/v1.2.3"
+        );
+    }
+
+    #[test]
+    fn test_resolve_version_not_found() {
+        let repo = RepoInfo::new("tftio", "peter-hook", "v");
+        let result = resolve_version(&repo, "9.9.9", |url| {
+            assert!(url.ends_with("/releases/tags/v9.9.9"));
+            Ok(false)
+        });
+        assert_eq!(result, Err(ExitCode::VersionNotFound.as_i32()));
+
+        let result = resolve_version(&repo, "9.9.9", |_| Err("HTTP 403".to_string()));
+        assert_eq!(result, Err(ExitCode::Failure.as_i32()));
+    }
+
     #[test]
     fn test_install_script_url_construction() {
         let repo = RepoInfo::new("tftio", "peter-hook", "v");
         let expected = "https://raw.githubusercontent.com/tftio/peter-hook/main/install.sh";
-        let actual = format!(
-            "https://raw.githubusercontent.com/{}/{}/main/install.sh",
-            repo.owner, repo.name
+        assert_eq!(repo.install_script_url(), expected);
+
+        let repo = RepoInfo::with_host("tftio", "peter-hook", "git.example.com");
+        let expected = "https://git.example.com/raw/tftio/peter-hook/main/install.sh";
+        assert_eq!(repo.install_script_url(), expected);
+    }
+
+    #[test]
+    fn test_offline_mode_skips_network() {
+        let _guard = offline_lock();
+        set_offline(true);
+        assert!(is_offline());
+        let repo = RepoInfo::new("tftio", "peter-hook", "v");
+        let code = run_update(&repo, "1.0.0", Some("1.2.3"), false, None, &[]);
+        assert_eq!(code, ExitCode::Failure.as_i32());
+        #[cfg(feature = "http")]
+        assert_eq!(check_for_update(&repo, "1.0.0"), Err(UpdateError::Offline));
+        #[cfg(feature = "self-update")]
+        assert_eq!(
+            run_update_native(&repo, "1.0.0", UpdateOptions::default()),
+            ExitCode::Failure.as_i32()
         );
-        assert_eq!(actual, expected);
+        set_offline(false);
+        assert!(!is_offline());
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_update_error_into_common_error() {
+        let err = CommonError::from(UpdateError::Network("timed out".into()));
+        assert_eq!(err.to_string(), "network error: timed out");
+        let err = CommonError::from(UpdateError::InvalidVersion("banana".into()));
+        assert_eq!(err.to_string(), "parse error: invalid version: banana");
+        assert!(matches!(
+            CommonError::from(UpdateError::Offline),
+            CommonError::Network(_)
+        ));
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("plain"), "'plain'");
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote("$(rm -rf /); `x`"), "'$(rm -rf /); `x`'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[test]
+    fn test_install_command_extra_args() {
+        let url = "https://example.com/install.sh";
+        assert_eq!(
+            install_command(url, &[]),
+            "curl -fsSL 'https://example.com/install.sh' | sh"
+        );
+        assert_eq!(
+            install_command(url, &["--prefix", "/opt/my tools"]),
+            "curl -fsSL 'https://example.com/install.sh' | sh -s -- '--prefix' '/opt/my tools'"
+        );
+    }
+
+    #[test]
+    fn test_install_env() {
+        let repo = RepoInfo::new("tftio", "peter-hook", "v");
+        let env = install_env(&repo, None, false, None);
+        assert_eq!(
+            env,
+            vec![
+                ("REPO_OWNER", OsString::from("tftio")),
+                ("REPO_NAME", OsString::from("peter-hook")),
+            ]
+        );
+
+        let repo = RepoInfo::with_host("tftio", "peter-hook", "git.example.com");
+        let pinned = ("v1.2.3".to_string(), "https://example.com/r".to_string());
+        let dir = Path::new("/opt/my tools;rm -rf x");
+        let env = install_env(&repo, Some(&pinned), true, Some(dir));
+        let keys: Vec<_> = env.iter().map(|(k, _)| *k).collect();
+        assert_eq!(
+            keys,
+            [
+                "REPO_OWNER",
+                "REPO_NAME",
+                "GITHUB_HOST",
+                "VERSION",
+                "RELEASE_URL",
+                "FORCE_INSTALL",
+                "INSTALL_DIR"
+            ]
+        );
+        assert_eq!(env[6].1, dir.as_os_str());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_install_dir_with_metacharacters_stays_literal() {
+        let repo = RepoInfo::new("tftio", "peter-hook", "v");
+        let dir = Path::new("/tmp/my dir; echo injected");
+        let output = Command::new("sh")
+            .args(["-c", "printf '%s' \"$INSTALL_DIR\""])
+            .envs(install_env(&repo, None, false, Some(dir)))
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "/tmp/my dir; echo injected"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_extra_args_are_not_word_split() {
+        let script = format!(
+            "set -- {}; printf '%s\\n' \"$#\" \"$@\"",
+            ["a b", "c;d", "it's"]
+                .iter()
+                .map(|a| shell_quote(a))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        let output = Command::new("sh").args(["-c", &script]).output().unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "3\na b\nc;d\nit's\n"
+        );
+    }
+
+    #[cfg(feature = "self-update")]
+    fn asset(name: &str) -> ReleaseAsset {
+        ReleaseAsset {
+            name: name.to_string(),
+            url: format!("https://example.com/download/{name}"),
+        }
+    }
+
+    #[cfg(feature = "self-update")]
+    fn fixture_assets() -> Vec<ReleaseAsset> {
+        vec![
+            asset("mytool-1.2.3-aarch64-apple-darwin"),
+            asset("mytool-1.2.3-aarch64-apple-darwin.sha256"),
+            asset("mytool-1.2.3-x86_64-unknown-linux-gnu.tar.gz"),
+            asset("mytool-1.2.3-x86_64-unknown-linux-musl"),
+            asset("mytool-1.2.3-x86_64-pc-windows-msvc.exe"),
+            asset("SHA256SUMS"),
+        ]
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_parse_release() {
+        let json = r#"{
+            "tag_name": "v1.2.3",
+            "assets": [
+                {"name": "mytool-x86_64-unknown-linux-gnu", "browser_download_url": "https://example.com/a"},
+                {"name": "broken"}
+            ]
+        }"#;
+        let release = parse_release(json).unwrap();
+        assert_eq!(release.tag, "v1.2.3");
+        assert_eq!(release.assets.len(), 1);
+        assert_eq!(release.assets[0].url, "https://example.com/a");
+        let repo = RepoInfo::new("workhelix", "prompter", "prompter-v");
+        assert_eq!(release_version(&repo, "prompter-v0.4.0"), "0.4.0");
+        assert_eq!(release_version(&repo, "v0.4.0"), "0.4.0");
+        let repo = RepoInfo::new("workhelix", "tool2", "tool2-v");
+        assert_eq!(release_version(&repo, "tool2-v1.0.0"), "1.0.0");
+
+        assert!(parse_release("{}").is_err());
+        assert!(parse_release("not json").is_err());
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_update_status() {
+        assert_eq!(
+            update_status("1.2.2", "1.2.3"),
+            Ok(UpdateStatus::Available {
+                latest: "1.2.3".to_string()
+            })
+        );
+        assert_eq!(
+            update_status("v1.0.0", "v1.2.3"),
+            Ok(UpdateStatus::Available {
+                latest: "1.2.3".to_string()
+            })
+        );
+        assert_eq!(update_status("1.2.3", "1.2.3"), Ok(UpdateStatus::UpToDate));
+        assert_eq!(update_status("2.0.0", "1.2.3"), Ok(UpdateStatus::Ahead));
+        assert_eq!(
+            update_status("1.0.0-rc1", "1.0.0"),
+            Ok(UpdateStatus::Available {
+                latest: "1.0.0".to_string()
+            })
+        );
+        assert_eq!(
+            update_status("dev", "1.0.0"),
+            Err(UpdateError::InvalidVersion("dev".to_string()))
+        );
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_github_token() {
+        crate::test_env::with_var("GITHUB_TOKEN", Some("secret"), || {
+            assert_eq!(github_token().as_deref(), Some("secret"));
+        });
+        crate::test_env::with_var("GITHUB_TOKEN", Some(" "), || {
+            assert_eq!(github_token(), None);
+        });
+    }
+
+    #[cfg(feature = "self-update")]
+    #[test]
+    fn test_select_asset() {
+        let assets = fixture_assets();
+        let pick = |target| select_asset(&assets, target).map(|a| a.name.as_str());
+
+        assert_eq!(
+            pick("aarch64-apple-darwin"),
+            Some("mytool-1.2.3-aarch64-apple-darwin")
+        );
+        assert_eq!(
+            pick("x86_64-pc-windows-msvc"),
+            Some("mytool-1.2.3-x86_64-pc-windows-msvc.exe")
+        );
+        // Archives are only picked when they can be unpacked; otherwise glibc
+        // falls back to musl
+        let linux = if cfg!(feature = "archive-tar") {
+            "mytool-1.2.3-x86_64-unknown-linux-gnu.tar.gz"
+        } else {
+            "mytool-1.2.3-x86_64-unknown-linux-musl"
+        };
+        assert_eq!(pick("x86_64-unknown-linux-gnu"), Some(linux));
+        assert_eq!(pick("riscv64gc-unknown-linux-gnu"), None);
+    }
+
+    #[cfg(feature = "self-update")]
+    #[test]
+    fn test_select_asset_prefers_bare_binary() {
+        let assets = vec![
+            asset("mytool-aarch64-apple-darwin.zip"),
+            asset("mytool-aarch64-apple-darwin"),
+        ];
+        let picked = select_asset(&assets, "aarch64-apple-darwin").unwrap();
+        assert_eq!(picked.name, "mytool-aarch64-apple-darwin");
+    }
+
+    #[cfg(feature = "self-update")]
+    #[test]
+    fn test_extract_binary_passes_bare_binary_through() {
+        let data = b"\x7fELF".to_vec();
+        assert_eq!(
+            extract_binary("mytool-x86_64-unknown-linux-gnu", data.clone(), "mytool"),
+            Ok(data)
+        );
+        assert!(extract_binary("mytool.tar.xz", Vec::new(), "mytool").is_err());
+    }
+
+    #[cfg(feature = "archive-tar")]
+    fn tar_gz_fixture(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (path, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder.append_data(&mut header, path, *contents).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[cfg(feature = "archive-tar")]
+    #[test]
+    fn test_extract_binary_from_tar_gz() {
+        let archive = tar_gz_fixture(&[
+            ("mytool-x86_64/README.md", b"docs"),
+            ("mytool-x86_64/mytool", b"binary"),
+        ]);
+        let name = "mytool-x86_64-unknown-linux-gnu.tar.gz";
+        assert_eq!(
+            extract_binary(name, archive.clone(), "mytool"),
+            Ok(b"binary".to_vec())
+        );
+        assert_eq!(
+            extract_binary(name, archive, "other"),
+            Err(format!("other not found in {name}"))
+        );
+        assert!(extract_binary(name, b"not gzip".to_vec(), "mytool").is_err());
+    }
+
+    #[cfg(feature = "archive-zip")]
+    #[test]
+    fn test_extract_binary_from_zip() {
+        use std::io::Write as _;
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        writer
+            .start_file(
+                "mytool/mytool.exe",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        writer.write_all(b"binary").unwrap();
+        let archive = writer.finish().unwrap().into_inner();
+
+        assert_eq!(
+            extract_binary("mytool-x86_64-pc-windows-msvc.zip", archive, "mytool.exe"),
+            Ok(b"binary".to_vec())
+        );
+    }
+
+    #[cfg(feature = "self-update")]
+    #[test]
+    fn test_find_checksum_asset() {
+        let assets = fixture_assets();
+        let find = |name| find_checksum_asset(&assets, name).map(|a| a.name.as_str());
+
+        assert_eq!(
+            find("mytool-1.2.3-aarch64-apple-darwin"),
+            Some("mytool-1.2.3-aarch64-apple-darwin.sha256")
+        );
+        assert_eq!(
+            find("mytool-1.2.3-x86_64-unknown-linux-musl"),
+            Some("SHA256SUMS")
+        );
+        assert_eq!(find_checksum_asset(&assets[..1], "anything"), None);
+    }
+
+    #[cfg(feature = "self-update")]
+    #[test]
+    fn test_parse_checksum() {
+        let hash = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+        assert_eq!(
+            parse_checksum(&format!("{hash}\n"), "any"),
+            Some(hash.to_string())
+        );
+        assert_eq!(
+            parse_checksum(&hash.to_uppercase(), "any"),
+            Some(hash.to_string())
+        );
+
+        let sums = format!(
+            "{}  mytool-x86_64-unknown-linux-musl\n{hash} *mytool-aarch64-apple-darwin\n",
+            "0".repeat(64)
+        );
+        assert_eq!(
+            parse_checksum(&sums, "mytool-aarch64-apple-darwin"),
+            Some(hash.to_string())
+        );
+        assert_eq!(
+            parse_checksum(&sums, "mytool-x86_64-unknown-linux-musl"),
+            Some("0".repeat(64))
+        );
+        assert_eq!(parse_checksum(&sums, "missing"), None);
+        assert_eq!(parse_checksum("not-a-hash", "any"), None);
+    }
+
+    #[cfg(feature = "self-update")]
+    #[test]
+    fn test_verify_sha256() {
+        let hash = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+        assert_eq!(sha256_hex(b"hello world"), hash);
+        assert!(verify_sha256(b"hello world", hash));
+        assert!(verify_sha256(b"hello world", &hash.to_uppercase()));
+        assert!(verify_sha256(b"hello world", &format!(" {hash}\n")));
+        assert!(!verify_sha256(b"hello world!", hash));
+        assert!(!verify_sha256(b"hello world", "not-a-hash"));
+    }
+
+    #[cfg(feature = "self-update")]
+    #[test]
+    fn test_parse_checksums_file() {
+        let hash = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+        let sums = format!(
+            "{}  mytool-x86_64-unknown-linux-musl\n\
+             {} *mytool-x86_64-pc-windows-msvc.exe\n\
+             \n\
+             not-a-hash  ignored\n\
+             {hash}\n",
+            hash.to_uppercase(),
+            "0".repeat(64)
+        );
+        let checksums = parse_checksums_file(&sums);
+
+        assert_eq!(checksums.len(), 2);
+        assert_eq!(checksums["mytool-x86_64-unknown-linux-musl"], hash);
+        assert_eq!(
+            checksums["mytool-x86_64-pc-windows-msvc.exe"],
+            "0".repeat(64)
+        );
+        assert!(parse_checksums_file("").is_empty());
+    }
+
+    #[cfg(feature = "self-update")]
+    #[test]
+    fn test_install_binary_replaces_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("mytool");
+        std::fs::write(&target, "old").unwrap();
+
+        install_binary(b"new", &target).unwrap();
+        assert_eq!(std::fs::read(&target).unwrap(), b"new");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&target).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
     }
 }
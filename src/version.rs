@@ -0,0 +1,264 @@
+//! Version output for `--version` and `version` commands.
+
+use std::cmp::Ordering;
+use std::io::{self, Write};
+
+use crate::types::{ExitCode, RepoInfo};
+
+/// Number of commit hash characters shown in version output.
+const SHORT_SHA_LEN: usize = 7;
+
+/// Format version output like `mytool 1.2.3`.
+///
+/// To include the commit the binary was built from, use
+/// [`version_string_with_commit`] with `option_env!("VERGEN_GIT_SHA")`.
+#[must_use]
+pub fn version_string(repo: &RepoInfo, version: &str) -> String {
+    version_string_with_commit(repo, version, None)
+}
+
+/// Format version output with an explicit commit hash.
+///
+/// # Examples
+/// ```
+/// use workhelix_cli_common::RepoInfo;
+/// use workhelix_cli_common::version::version_string_with_commit;
+///
+/// let repo = RepoInfo::new("myorg", "mytool", "v");
+/// let version = version_string_with_commit(&repo, "1.2.3", option_env!("VERGEN_GIT_SHA"));
+/// assert!(version.starts_with("mytool 1.2.3"));
+/// ```
+#[must_use]
+pub fn version_string_with_commit(repo: &RepoInfo, version: &str, commit: Option<&str>) -> String {
+    let mut output = format!("{} {version}", repo.name);
+    if let Some(sha) = commit.map(str::trim).filter(|sha| !sha.is_empty()) {
+        output.push_str(" (");
+        output.extend(sha.chars().take(SHORT_SHA_LEN));
+        output.push(')');
+    }
+    output
+}
+
+/// Compare two version strings by semver precedence.
+///
+/// A leading `v` or a release tag prefix such as `mytool-v` is ignored, and
+/// missing minor or patch components count as zero, so `v2.30` equals
+/// `2.30.0`. Pre-releases sort before their release (`1.0.0-rc1 < 1.0.0`) and
+/// build metadata is ignored. Returns `None` if either version can't be parsed.
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering;
+/// use workhelix_cli_common::version::compare_versions;
+///
+/// assert_eq!(compare_versions("mytool-v1.2.0", "1.10.0"), Some(Ordering::Less));
+/// assert_eq!(compare_versions("1.0.0-rc1", "1.0.0"), Some(Ordering::Less));
+/// assert_eq!(compare_versions("latest", "1.0.0"), None);
+/// ```
+#[must_use]
+pub fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    let mut a = parse_version(a)?;
+    let mut b = parse_version(b)?;
+    a.build = semver::BuildMetadata::EMPTY;
+    b.build = semver::BuildMetadata::EMPTY;
+    Some(a.cmp(&b))
+}
+
+/// Parse a version string leniently; see [`compare_versions`] for the rules.
+pub(crate) fn parse_version(version: &str) -> Option<semver::Version> {
+    let version = version.trim();
+    // Try the whole string first, then each suffix that follows a tag prefix
+    // separator (`v`, `-`, `_` or `/`), so `mytool-v1.2.3` yields `1.2.3`.
+    let starts = std::iter::once(0).chain(
+        version
+            .char_indices()
+            .filter(|(_, c)| matches!(c, 'v' | 'V' | '-' | '_' | '/'))
+            .map(|(i, c)| i + c.len_utf8()),
+    );
+    for start in starts {
+        let candidate = &version[start..];
+        if candidate.starts_with(|c: char| c.is_ascii_digit()) {
+            if let Some(parsed) = parse_padded(candidate) {
+                return Some(parsed);
+            }
+        }
+    }
+    None
+}
+
+/// Parse `MAJOR[.MINOR[.PATCH]][-PRE][+BUILD]`, padding missing components with zero.
+fn parse_padded(version: &str) -> Option<semver::Version> {
+    if let Ok(parsed) = semver::Version::parse(version) {
+        return Some(parsed);
+    }
+    let split = version.find(['-', '+']).unwrap_or(version.len());
+    let (core, rest) = version.split_at(split);
+    let parts = core.split('.').count();
+    if parts >= 3 || core.split('.').any(str::is_empty) {
+        return None;
+    }
+    let padding = ".0".repeat(3 - parts);
+    semver::Version::parse(&format!("{core}{padding}{rest}")).ok()
+}
+
+/// Print version output to stdout.
+///
+/// See [`version_string`] for the format.
+pub fn print_version(repo: &RepoInfo, version: &str) {
+    println!("{}", version_string(repo, version));
+}
+
+/// Handle a `version` subcommand or `--version` flag.
+///
+/// Prints [`version_string`] to stdout. When `check_update` is true and the
+/// `http` feature is enabled, the latest release is queried with
+/// [`check_for_update`](crate::update::check_for_update) and
+/// `(update available: x.y.z)` is appended if it is newer. A failed check
+/// (e.g. no network) is ignored, so the version is always printed.
+///
+/// Returns the process exit code, which is always success.
+#[must_use]
+pub fn handle_version_command(repo: &RepoInfo, version: &str, check_update: bool) -> i32 {
+    version_command(&mut io::stdout().lock(), repo, version, || {
+        check_update
+            .then(|| available_update(repo, version))
+            .flatten()
+    })
+}
+
+/// The newer release reported by the update check, if any.
+#[cfg(feature = "http")]
+fn available_update(repo: &RepoInfo, version: &str) -> Option<String> {
+    match crate::update::check_for_update(repo, version) {
+        Ok(crate::update::UpdateStatus::Available { latest }) => Some(latest),
+        _ => None,
+    }
+}
+
+/// Without the `http` feature there is no update check.
+#[cfg(not(feature = "http"))]
+const fn available_update(_repo: &RepoInfo, _version: &str) -> Option<String> {
+    None
+}
+
+/// Write the version line, with the update nudge if `latest` reports one.
+fn version_command(
+    out: &mut impl Write,
+    repo: &RepoInfo,
+    version: &str,
+    latest: impl FnOnce() -> Option<String>,
+) -> i32 {
+    let line = version_string(repo, version);
+    let _ = match latest() {
+        Some(latest) => writeln!(out, "{line} (update available: {latest})"),
+        None => writeln!(out, "{line}"),
+    };
+    ExitCode::Success.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_string() {
+        let repo = RepoInfo::new("workhelix", "prompter", "prompter-v");
+        assert_eq!(version_string(&repo, "1.2.3"), "prompter 1.2.3");
+        crate::test_env::with_var("VERGEN_GIT_SHA", Some("0123456789abcdef"), || {
+            assert_eq!(version_string(&repo, "1.2.3"), "prompter 1.2.3");
+        });
+        assert_eq!(
+            version_string_with_commit(&repo, "1.2.3", Some("0123456789abcdef")),
+            "prompter 1.2.3 (0123456)"
+        );
+    }
+
+    #[test]
+    fn test_version_command_update_nudge() {
+        let repo = RepoInfo::new("workhelix", "prompter", "prompter-v");
+        let mut out = Vec::new();
+        let code = version_command(&mut out, &repo, "1.2.3", || Some("1.3.0".to_string()));
+        assert_eq!(code, 0);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "prompter 1.2.3 (update available: 1.3.0)\n"
+        );
+    }
+
+    #[test]
+    fn test_version_command_failed_check_still_prints() {
+        let _guard = crate::test_env::offline_lock();
+        let repo = RepoInfo::new("workhelix", "prompter", "prompter-v");
+        crate::set_offline(true);
+        // The offline check fails without touching the network
+        let mut out = Vec::new();
+        let code = version_command(&mut out, &repo, "1.2.3", || {
+            available_update(&repo, "1.2.3")
+        });
+        crate::set_offline(false);
+        assert_eq!(code, 0);
+        assert_eq!(String::from_utf8(out).unwrap(), "prompter 1.2.3\n");
+    }
+
+    #[test]
+    fn test_compare_versions_prerelease() {
+        assert_eq!(compare_versions("1.0.0-rc1", "1.0.0"), Some(Ordering::Less));
+        assert_eq!(
+            compare_versions("1.0.0-rc.2", "1.0.0-rc.10"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_versions("1.0.0-alpha", "1.0.0-beta"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(compare_versions("1.10.0", "1.9.9"), Some(Ordering::Greater));
+        assert_eq!(
+            compare_versions("1.0.0+build.5", "1.0.0"),
+            Some(Ordering::Equal)
+        );
+    }
+
+    #[test]
+    fn test_compare_versions_prefixes() {
+        assert_eq!(compare_versions("v1.2.3", "1.2.3"), Some(Ordering::Equal));
+        assert_eq!(
+            compare_versions("prompter-v1.2.3", "v1.2.3"),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            compare_versions("tool2-v1.0.0", "1.0.0"),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            compare_versions("release/2.0.0", "1.0.0"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(compare_versions("v2.30", "2.30.0"), Some(Ordering::Equal));
+        assert_eq!(compare_versions("3", "2.99.99"), Some(Ordering::Greater));
+    }
+
+    #[test]
+    fn test_compare_versions_unparseable() {
+        assert_eq!(compare_versions("latest", "1.0.0"), None);
+        assert_eq!(compare_versions("1.0.0", ""), None);
+        assert_eq!(compare_versions("1..0", "1.0.0"), None);
+        assert_eq!(compare_versions("1.2.3.4", "1.2.3"), None);
+    }
+
+    #[test]
+    fn test_version_string_with_commit() {
+        let repo = RepoInfo::new("workhelix", "prompter", "prompter-v");
+        assert_eq!(
+            version_string_with_commit(&repo, "0.4.0", Some("abc")),
+            "prompter 0.4.0 (abc)"
+        );
+        assert_eq!(
+            version_string_with_commit(&repo, "0.4.0", Some("  ")),
+            "prompter 0.4.0"
+        );
+        assert_eq!(
+            version_string_with_commit(&repo, "0.4.0", None),
+            "prompter 0.4.0"
+        );
+    }
+}